@@ -0,0 +1,164 @@
+//! Constraints on nondet accounts
+use cvlr_asserts::cvlr_assume;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+use crate::layout::{cvlr_deserialize_nondet_accounts_n, cvlr_new_account_info_sized, DataSize};
+
+/// Builder of a nondet [`AccountInfo`] over [`crate::cvlr_new_account_info`]
+///
+/// Each constraint adds exactly one assumption on the account. Properties
+/// that are not constrained stay nondet.
+///
+/// ```ignore
+/// let vault = NondetAccount::new()
+///     .writable(true)
+///     .owner(&spl_token::ID)
+///     .data_len(165)
+///     .key_distinct_from(&[*authority.key])
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct NondetAccount {
+    key: Option<Pubkey>,
+    signer: Option<bool>,
+    writable: Option<bool>,
+    executable: Option<bool>,
+    owner: Option<Pubkey>,
+    data_size: DataSize,
+    min_lamports: Option<u64>,
+    max_lamports: Option<u64>,
+    distinct_from: Vec<Pubkey>,
+}
+
+impl Default for NondetAccount {
+    fn default() -> Self {
+        Self {
+            key: None,
+            signer: None,
+            writable: None,
+            executable: None,
+            owner: None,
+            data_size: DataSize::any(),
+            min_lamports: None,
+            max_lamports: None,
+            distinct_from: Vec::new(),
+        }
+    }
+}
+
+impl NondetAccount {
+    /// Builder without constraints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Key equal to [key]
+    pub fn key(mut self, key: &Pubkey) -> Self {
+        self.key = Some(*key);
+        self
+    }
+
+    /// Signer flag equal to [is_signer]
+    pub fn signer(mut self, is_signer: bool) -> Self {
+        self.signer = Some(is_signer);
+        self
+    }
+
+    /// Writable flag equal to [is_writable]
+    pub fn writable(mut self, is_writable: bool) -> Self {
+        self.writable = Some(is_writable);
+        self
+    }
+
+    /// Executable flag equal to [executable]
+    pub fn executable(mut self, executable: bool) -> Self {
+        self.executable = Some(executable);
+        self
+    }
+
+    /// Owned by [owner]
+    pub fn owner(mut self, owner: &Pubkey) -> Self {
+        self.owner = Some(*owner);
+        self
+    }
+
+    /// Data of exactly [len] bytes
+    pub fn data_len(self, len: usize) -> Self {
+        self.data_size(DataSize::exact(len))
+    }
+
+    /// Data length within [size]
+    pub fn data_size(mut self, size: DataSize) -> Self {
+        self.data_size = size;
+        self
+    }
+
+    /// At least [lamports] lamports
+    pub fn lamports_at_least(mut self, lamports: u64) -> Self {
+        self.min_lamports = Some(lamports);
+        self
+    }
+
+    /// At most [lamports] lamports
+    pub fn lamports_at_most(mut self, lamports: u64) -> Self {
+        self.max_lamports = Some(lamports);
+        self
+    }
+
+    /// Key different from every key in [keys]
+    pub fn key_distinct_from(mut self, keys: &[Pubkey]) -> Self {
+        self.distinct_from.extend_from_slice(keys);
+        self
+    }
+
+    /// Returns a nondet account under the constraints of this builder
+    pub fn build(&self) -> AccountInfo<'static> {
+        let info = cvlr_new_account_info_sized(self.data_size);
+        if let Some(key) = self.key {
+            cvlr_assume!(*info.key == key);
+        }
+        if let Some(is_signer) = self.signer {
+            cvlr_assume!(info.is_signer == is_signer);
+        }
+        if let Some(is_writable) = self.writable {
+            cvlr_assume!(info.is_writable == is_writable);
+        }
+        if let Some(executable) = self.executable {
+            cvlr_assume!(info.executable == executable);
+        }
+        if let Some(owner) = self.owner {
+            cvlr_assume!(*info.owner == owner);
+        }
+        if let Some(lamports) = self.min_lamports {
+            cvlr_assume!(info.lamports() >= lamports);
+        }
+        if let Some(lamports) = self.max_lamports {
+            cvlr_assume!(info.lamports() <= lamports);
+        }
+        cvlr_assume_keys_distinct_from(&info, &self.distinct_from);
+        info
+    }
+}
+
+/// Assumes that the keys of [infos] are pairwise distinct
+pub fn cvlr_assume_distinct_keys(infos: &[AccountInfo]) {
+    for (i, info) in infos.iter().enumerate() {
+        for other in &infos[i + 1..] {
+            cvlr_assume!(info.key != other.key);
+        }
+    }
+}
+
+/// Assumes that the key of [info] is none of [keys]
+pub fn cvlr_assume_keys_distinct_from(info: &AccountInfo, keys: &[Pubkey]) {
+    for key in keys {
+        cvlr_assume!(info.key != key);
+    }
+}
+
+/// Returns `N` nondet accounts with pairwise distinct keys
+pub fn cvlr_deserialize_distinct_nondet_accounts<const N: usize>() -> [AccountInfo<'static>; N] {
+    let accs = cvlr_deserialize_nondet_accounts_n::<N>();
+    cvlr_assume_distinct_keys(&accs);
+    accs
+}
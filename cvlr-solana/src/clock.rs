@@ -1,7 +1,68 @@
 use cvlr_nondet::nondet;
-use solana_program::clock::Slot;
+use solana_program::clock::{Clock, Epoch, Slot, UnixTimestamp};
 
 static mut CVT_CLOCK_SLOT: Option<Slot> = None;
+static mut CVT_CLOCK_UNIX_TIMESTAMP: Option<UnixTimestamp> = None;
+static mut CVT_CLOCK_EPOCH: Option<ClockEpoch> = None;
+static mut CVT_CLOCK_BOUNDS: Option<ClockBounds> = None;
+
+/// Bounds on the progression of the clock model
+///
+/// By default, the clock model only guarantees that time moves forward. Once
+/// bounds are set with [`cvlr_clock_set_bounds`], every advance of the clock
+/// is at most `max_slot_delta` slots and `max_unix_timestamp_delta` seconds,
+/// and the clock never goes past `max_slot` and `max_unix_timestamp`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockBounds {
+    pub max_slot_delta: u64,
+    pub max_unix_timestamp_delta: u64,
+    pub max_slot: Slot,
+    pub max_unix_timestamp: UnixTimestamp,
+}
+
+impl Default for ClockBounds {
+    /// One year per advance, and nothing beyond year 2100
+    fn default() -> Self {
+        const SECS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+        // -- 2100-01-01T00:00:00Z
+        const MAX_UNIX_TIMESTAMP: UnixTimestamp = 4_102_444_800;
+        Self {
+            // -- at 400ms per slot
+            max_slot_delta: SECS_PER_YEAR * 5 / 2,
+            max_unix_timestamp_delta: SECS_PER_YEAR,
+            // -- slots of 400ms since 1970, about 2^33
+            max_slot: MAX_UNIX_TIMESTAMP as Slot * 5 / 2,
+            max_unix_timestamp: MAX_UNIX_TIMESTAMP,
+        }
+    }
+}
+
+/// Enables bounded time progression, or disables it if [bounds] is `None`
+pub fn cvlr_clock_set_bounds(bounds: Option<ClockBounds>) {
+    unsafe {
+        CVT_CLOCK_BOUNDS = bounds;
+    }
+}
+
+#[inline(always)]
+fn assume_slot_bounds(old_slot: Option<Slot>, new_slot: Slot) {
+    if let Some(bounds) = unsafe { CVT_CLOCK_BOUNDS } {
+        cvlr_asserts::cvlr_assume!(new_slot <= bounds.max_slot);
+        if let Some(old_slot) = old_slot {
+            cvlr_asserts::cvlr_assume!(new_slot - old_slot <= bounds.max_slot_delta);
+        }
+    }
+}
+
+#[inline(always)]
+fn assume_unix_timestamp_bounds(old_ts: Option<UnixTimestamp>, new_ts: UnixTimestamp) {
+    if let Some(bounds) = unsafe { CVT_CLOCK_BOUNDS } {
+        cvlr_asserts::cvlr_assume!(new_ts <= bounds.max_unix_timestamp);
+        if let Some(old_ts) = old_ts {
+            cvlr_asserts::cvlr_assume!((new_ts - old_ts) as u64 <= bounds.max_unix_timestamp_delta);
+        }
+    }
+}
 
 // The prover by default does not inline functions that start with
 // prefix "cvt_".  We could tell the prover to inline these functions
@@ -15,6 +76,7 @@ pub fn cvt_get_next_clock_slot() -> Slot {
         if let Some(old_slot) = CVT_CLOCK_SLOT {
             cvlr_asserts::cvlr_assume!(new_slot > old_slot);
         }
+        assume_slot_bounds(CVT_CLOCK_SLOT, new_slot);
         CVT_CLOCK_SLOT = Some(new_slot);
         new_slot
     }
@@ -27,3 +89,173 @@ pub fn cvt_get_clock_slot() -> Slot {
     cvlr_asserts::cvlr_assert!(unsafe { CVT_CLOCK_SLOT.is_some() });
     unsafe { CVT_CLOCK_SLOT.unwrap() }
 }
+
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn cvt_get_next_clock_unix_timestamp() -> UnixTimestamp {
+    unsafe {
+        let new_ts = nondet::<i64>();
+        cvlr_asserts::cvlr_assume!(new_ts >= 0);
+        // several slots can share the same timestamp
+        if let Some(old_ts) = CVT_CLOCK_UNIX_TIMESTAMP {
+            cvlr_asserts::cvlr_assume!(new_ts >= old_ts);
+        }
+        assume_unix_timestamp_bounds(CVT_CLOCK_UNIX_TIMESTAMP, new_ts);
+        CVT_CLOCK_UNIX_TIMESTAMP = Some(new_ts);
+        new_ts
+    }
+}
+
+#[allow(non_snake_case, static_mut_refs)]
+#[inline(always)]
+pub fn cvt_get_clock_unix_timestamp() -> UnixTimestamp {
+    // need to call at least once cvt_get_next_clock_unix_timestamp before calling this function
+    cvlr_asserts::cvlr_assert!(unsafe { CVT_CLOCK_UNIX_TIMESTAMP.is_some() });
+    unsafe { CVT_CLOCK_UNIX_TIMESTAMP.unwrap() }
+}
+
+/// Returns a nondet [`Clock`] whose `slot` and `unix_timestamp` agree with
+/// the clock model.
+///
+/// If the clock model has not been advanced yet, it is advanced once so that
+/// every subsequent call observes the same slot and timestamp. The epoch
+/// fields are drawn once per slot: calls that observe the same slot observe
+/// the same epoch, and epochs never go back when the slot moves forward.
+pub fn cvlr_nondet_clock() -> Clock {
+    let (slot, unix_timestamp) = unsafe {
+        (
+            CVT_CLOCK_SLOT.unwrap_or_else(cvt_get_next_clock_slot),
+            CVT_CLOCK_UNIX_TIMESTAMP.unwrap_or_else(cvt_get_next_clock_unix_timestamp),
+        )
+    };
+
+    let old_epoch = unsafe { CVT_CLOCK_EPOCH };
+    let clock_epoch = match old_epoch {
+        Some(old_epoch) if old_epoch.slot == slot => old_epoch,
+        _ => {
+            let epoch: Epoch = nondet();
+            let leader_schedule_epoch: Epoch = nondet();
+            let epoch_start_timestamp: UnixTimestamp = nondet();
+            cvlr_asserts::cvlr_assume!(epoch <= leader_schedule_epoch);
+            cvlr_asserts::cvlr_assume!(epoch_start_timestamp >= 0);
+            cvlr_asserts::cvlr_assume!(epoch_start_timestamp <= unix_timestamp);
+            if let Some(old_epoch) = old_epoch {
+                if old_epoch.slot < slot {
+                    cvlr_asserts::cvlr_assume!(epoch >= old_epoch.epoch);
+                    cvlr_asserts::cvlr_assume!(
+                        leader_schedule_epoch >= old_epoch.leader_schedule_epoch
+                    );
+                    cvlr_asserts::cvlr_assume!(
+                        epoch_start_timestamp >= old_epoch.epoch_start_timestamp
+                    );
+                    if epoch == old_epoch.epoch {
+                        cvlr_asserts::cvlr_assume!(
+                            epoch_start_timestamp == old_epoch.epoch_start_timestamp
+                        );
+                    }
+                }
+            }
+            let clock_epoch = ClockEpoch {
+                slot,
+                epoch,
+                leader_schedule_epoch,
+                epoch_start_timestamp,
+            };
+            unsafe {
+                CVT_CLOCK_EPOCH = Some(clock_epoch);
+            }
+            clock_epoch
+        }
+    };
+
+    Clock {
+        slot,
+        epoch_start_timestamp: clock_epoch.epoch_start_timestamp,
+        epoch: clock_epoch.epoch,
+        leader_schedule_epoch: clock_epoch.leader_schedule_epoch,
+        unix_timestamp,
+    }
+}
+
+/// The epoch fields of the [`Clock`] last returned by [`cvlr_nondet_clock`],
+/// and the slot they were drawn for
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClockEpoch {
+    pub slot: Slot,
+    pub epoch: Epoch,
+    pub leader_schedule_epoch: Epoch,
+    pub epoch_start_timestamp: UnixTimestamp,
+}
+
+/// A snapshot of the clock model
+///
+/// A field is `None` if the corresponding part of the clock model has not
+/// been initialized yet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClockState {
+    pub slot: Option<Slot>,
+    pub unix_timestamp: Option<UnixTimestamp>,
+    pub epoch: Option<ClockEpoch>,
+}
+
+/// Returns the current state of the clock model
+pub fn cvlr_clock_snapshot() -> ClockState {
+    unsafe {
+        ClockState {
+            slot: CVT_CLOCK_SLOT,
+            unix_timestamp: CVT_CLOCK_UNIX_TIMESTAMP,
+            epoch: CVT_CLOCK_EPOCH,
+        }
+    }
+}
+
+/// Sets the clock model to a state previously returned by [`cvlr_clock_snapshot`]
+pub fn cvlr_clock_restore(state: ClockState) {
+    unsafe {
+        CVT_CLOCK_SLOT = state.slot;
+        CVT_CLOCK_UNIX_TIMESTAMP = state.unix_timestamp;
+        CVT_CLOCK_EPOCH = state.epoch;
+    }
+}
+
+/// Forgets the clock model, as if no slot or timestamp were ever observed
+///
+/// Use [`crate::cvlr_sysvars_reset`] to forget the rent and epoch schedule.
+pub fn cvlr_clock_reset() {
+    cvlr_clock_restore(ClockState::default());
+}
+
+/// Advances the current slot by a nondet number of slots in `[min, max]`
+///
+/// If the clock model has not been initialized, it starts from an arbitrary
+/// slot.
+pub fn cvlr_clock_advance_by(min: u64, max: u64) -> Slot {
+    unsafe {
+        let old_slot = CVT_CLOCK_SLOT.unwrap_or_else(nondet::<u64>);
+        let new_slot = Slot::from(nondet::<u64>());
+        cvlr_asserts::cvlr_assume!(new_slot >= old_slot);
+        cvlr_asserts::cvlr_assume!(new_slot - old_slot >= min);
+        cvlr_asserts::cvlr_assume!(new_slot - old_slot <= max);
+        assume_slot_bounds(Some(old_slot), new_slot);
+        CVT_CLOCK_SLOT = Some(new_slot);
+        new_slot
+    }
+}
+
+/// Advances the current unix timestamp by a nondet number of seconds in `[min, max]`
+///
+/// If the clock model has not been initialized, it starts from an arbitrary
+/// non-negative timestamp.
+pub fn cvlr_clock_advance_unix_timestamp_by(min: u64, max: u64) -> UnixTimestamp {
+    unsafe {
+        let old_ts = CVT_CLOCK_UNIX_TIMESTAMP.unwrap_or_else(nondet::<i64>);
+        cvlr_asserts::cvlr_assume!(old_ts >= 0);
+        let new_ts = nondet::<i64>();
+        cvlr_asserts::cvlr_assume!(new_ts >= old_ts);
+        cvlr_asserts::cvlr_assume!((new_ts - old_ts) as u64 >= min);
+        cvlr_asserts::cvlr_assume!((new_ts - old_ts) as u64 <= max);
+        assume_unix_timestamp_bounds(Some(old_ts), new_ts);
+        CVT_CLOCK_UNIX_TIMESTAMP = Some(new_ts);
+        new_ts
+    }
+}
@@ -0,0 +1,396 @@
+//! Model of cross-program invocations (CPI)
+//!
+//! The `invoke!` and `invoke_signed!` macros of this crate call
+//! [`cvlr_invoke_signed`]. By default, it performs no check and calls
+//! `invoke_signed_unchecked`. Checks of the runtime CPI rules are enabled with
+//! [`cvlr_cpi_set_checks`].
+//!
+//! A harness replaces the callee of a CPI by a summary registered for its
+//! program id with [`cvlr_cpi_register`]. Summaries of the System, Token,
+//! Token-2022 and Associated Token Account programs are registered by
+//! [`cvlr_cpi_register_builtins`]. A CPI into a program without summary is
+//! handled as set by [`cvlr_cpi_set_default`].
+
+use cvlr_asserts::{cvlr_assert, cvlr_assume};
+use cvlr_nondet::nondet;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    instruction::Instruction,
+    program::invoke_signed_unchecked,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{cvlr_create_program_address, cvlr_reset_return_data, cvlr_set_nondet_return_data};
+
+/// Checks of the runtime CPI rules performed by [`cvlr_invoke_signed`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CpiChecks {
+    /// Derive the PDA of each set of signer seeds with the PDA model, and
+    /// assert that every account required to sign either signed the
+    /// transaction or is one of these PDAs
+    pub signer_seeds: bool,
+    /// Re-implement the runtime privilege rules: assert that every account of
+    /// the instruction is passed, and that it is not given signer or writable
+    /// privileges that the caller does not have
+    pub privileges: bool,
+}
+
+static mut CVT_CPI_CHECKS: CpiChecks = CpiChecks {
+    signer_seeds: false,
+    privileges: false,
+};
+static mut CVT_CURRENT_PROGRAM_ID: Option<Pubkey> = None;
+
+/// Maximum number of nested CPIs below the top-level instruction
+pub const MAX_CPI_DEPTH: usize = 4;
+
+/// Program ids of the CPIs in progress, innermost last
+static mut CVT_INVOKE_STACK: Vec<Pubkey> = Vec::new();
+static mut CVT_CPI_SELF_CALL: bool = false;
+
+/// Summary of the program called by a CPI
+///
+/// It takes the same arguments as `invoke_signed` and updates
+/// the accounts as the program would.
+pub type CpiSummary = fn(&Instruction, &[AccountInfo], &[&[&[u8]]]) -> ProgramResult;
+
+/// How [`cvlr_invoke_signed`] handles a CPI into a program without summary
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CpiDefault {
+    /// Call the program with `invoke_signed_unchecked`
+    #[default]
+    Invoke,
+    /// Rewrite the lamports and data of writable accounts with nondet values,
+    /// and succeed
+    HavocWritable,
+    /// Succeed without changing any account
+    AssumeSuccess,
+    /// Succeed or fail with a nondet error, without changing any account
+    NondetResult,
+    /// Call [`cvlr_havoc_cpi`]
+    Havoc,
+}
+
+static mut CVT_CPI_TARGETS: Vec<Pubkey> = Vec::new();
+static mut CVT_CPI_SUMMARIES: Vec<(Pubkey, CpiSummary)> = Vec::new();
+static mut CVT_CPI_DEFAULT: CpiDefault = CpiDefault::Invoke;
+
+/// Enables or disables checks of the runtime CPI rules
+pub fn cvlr_cpi_set_checks(checks: CpiChecks) {
+    unsafe {
+        CVT_CPI_CHECKS = checks;
+    }
+}
+
+/// Sets the id of the program under verification
+///
+/// PDA signers of a CPI are derived from this program id.
+pub fn cvlr_set_current_program_id(program_id: &Pubkey) {
+    unsafe {
+        CVT_CURRENT_PROGRAM_ID = Some(*program_id);
+    }
+}
+
+/// Returns the id of the executing program: the callee of the innermost CPI
+/// in progress, or the program under verification
+#[allow(static_mut_refs)]
+pub fn cvlr_current_program_id() -> Pubkey {
+    if let Some(program_id) = unsafe { CVT_INVOKE_STACK.last() } {
+        return *program_id;
+    }
+    // need to call cvlr_set_current_program_id before calling this function
+    cvlr_assert!(unsafe { CVT_CURRENT_PROGRAM_ID.is_some() });
+    unsafe { CVT_CURRENT_PROGRAM_ID.unwrap() }
+}
+
+/// Returns the PDAs that sign with [signers_seeds] on behalf of the current program
+fn derive_pda_signers(signers_seeds: &[&[&[u8]]]) -> Result<Vec<Pubkey>, ProgramError> {
+    let mut signers = Vec::with_capacity(signers_seeds.len());
+    if signers_seeds.is_empty() {
+        return Ok(signers);
+    }
+    let program_id = cvlr_current_program_id();
+    for seeds in signers_seeds {
+        let address = cvlr_create_program_address(seeds, &program_id)?;
+        signers.push(address);
+    }
+    Ok(signers)
+}
+
+/// Asserts that every signer of [instruction] signed the transaction or is
+/// one of the [pda_signers]
+fn check_signers(instruction: &Instruction, account_infos: &[AccountInfo], pda_signers: &[Pubkey]) {
+    for meta in instruction.accounts.iter().filter(|meta| meta.is_signer) {
+        let is_signer = pda_signers.contains(&meta.pubkey)
+            || account_infos
+                .iter()
+                .any(|info| *info.key == meta.pubkey && info.is_signer);
+        cvlr_assert!(
+            is_signer,
+            "CPI signer is neither a signer nor a PDA of the caller"
+        );
+    }
+}
+
+/// Asserts that [instruction] does not escalate the privileges of
+/// [account_infos], where the [pda_signers] sign on behalf of the caller
+fn check_privileges(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    pda_signers: &[Pubkey],
+) {
+    for meta in &instruction.accounts {
+        let info = account_infos.iter().find(|info| *info.key == meta.pubkey);
+        cvlr_assert!(info.is_some(), "MissingAccount: CPI account is not passed");
+        if let Some(info) = info {
+            if meta.is_signer {
+                let is_signer = info.is_signer || pda_signers.contains(&meta.pubkey);
+                cvlr_assert!(is_signer, "PrivilegeEscalation: CPI signer is not a signer");
+            }
+            if meta.is_writable {
+                cvlr_assert!(
+                    info.is_writable,
+                    "PrivilegeEscalation: CPI writable account is not writable"
+                );
+            }
+        }
+    }
+}
+
+/// Returns the program id of every CPI of this run, in order
+#[allow(static_mut_refs)]
+pub fn cvlr_cpi_targets() -> Vec<Pubkey> {
+    unsafe { CVT_CPI_TARGETS.clone() }
+}
+
+/// Asserts that every CPI of this run targets one of the [allowed] programs
+///
+/// A program that invokes a program id taken from an unchecked account fails
+/// this assertion.
+#[allow(static_mut_refs)]
+pub fn cvlr_assert_cpi_target_in(allowed: &[Pubkey]) {
+    for program_id in unsafe { CVT_CPI_TARGETS.iter() } {
+        cvlr_assert!(
+            allowed.contains(program_id),
+            "CPI into a program outside of the allowed set"
+        );
+    }
+}
+
+/// Returns the number of CPIs in progress
+#[allow(static_mut_refs)]
+pub fn cvlr_cpi_depth() -> usize {
+    unsafe { CVT_INVOKE_STACK.len() }
+}
+
+/// Asserts that no CPI of this run targeted a program that was executing,
+/// including a program calling itself
+///
+/// Self-calls of the program under verification are only detected if
+/// [`cvlr_set_current_program_id`] was called before its CPIs, so this also
+/// asserts that it was.
+#[allow(static_mut_refs)]
+pub fn cvlr_assert_no_self_cpi() {
+    // need to call cvlr_set_current_program_id before calling this function
+    cvlr_assert!(unsafe { CVT_CURRENT_PROGRAM_ID.is_some() });
+    cvlr_assert!(
+        unsafe { !CVT_CPI_SELF_CALL },
+        "CPI into a program that is already executing"
+    );
+}
+
+/// Forgets the CPIs of this run
+#[allow(static_mut_refs)]
+pub fn cvlr_cpi_reset_targets() {
+    unsafe {
+        CVT_CPI_TARGETS.clear();
+        CVT_CPI_SELF_CALL = false;
+    }
+}
+
+/// Registers [summary] for every CPI into [program_id], replacing any
+/// previously registered summary
+#[allow(static_mut_refs)]
+pub fn cvlr_cpi_register(program_id: &Pubkey, summary: CpiSummary) {
+    let summaries = unsafe { &mut CVT_CPI_SUMMARIES };
+    match summaries.iter_mut().find(|(id, _)| id == program_id) {
+        Some(entry) => entry.1 = summary,
+        None => summaries.push((*program_id, summary)),
+    }
+}
+
+/// Registers the summaries of the System, Token, Token-2022 and Associated
+/// Token Account programs
+pub fn cvlr_cpi_register_builtins() {
+    cvlr_cpi_register(
+        &solana_program::system_program::ID,
+        crate::system::system_program_cpi,
+    );
+    cvlr_cpi_register(&spl_token::ID, crate::token::spl_token_cpi);
+    cvlr_cpi_register(&spl_token_2022::ID, crate::token::spl_token_2022_cpi);
+    cvlr_cpi_register(
+        &crate::token::ASSOCIATED_TOKEN_PROGRAM_ID,
+        crate::token::spl_associated_token_account_cpi,
+    );
+}
+
+/// Sets how a CPI into a program without summary is handled
+pub fn cvlr_cpi_set_default(default: CpiDefault) {
+    unsafe {
+        CVT_CPI_DEFAULT = default;
+    }
+}
+
+#[allow(static_mut_refs)]
+fn find_summary(program_id: &Pubkey) -> Option<CpiSummary> {
+    unsafe { CVT_CPI_SUMMARIES.iter() }
+        .find(|(id, _)| id == program_id)
+        .map(|(_, summary)| *summary)
+}
+
+/// Handles a CPI as set by [`cvlr_cpi_set_default`]
+///
+/// Summaries call it for the instructions they do not model.
+pub fn cvlr_cpi_default(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    match unsafe { CVT_CPI_DEFAULT } {
+        CpiDefault::Invoke => invoke_signed_unchecked(instruction, account_infos, signers_seeds),
+        CpiDefault::HavocWritable => havoc_writable(instruction, account_infos, false),
+        CpiDefault::Havoc => cvlr_havoc_cpi(instruction, account_infos),
+        CpiDefault::AssumeSuccess => Ok(()),
+        CpiDefault::NondetResult => {
+            if nondet() {
+                Ok(())
+            } else {
+                Err(ProgramError::Custom(nondet()))
+            }
+        }
+    }
+}
+
+/// Rewrites [data] with nondet bytes at once
+#[cfg(not(feature = "rt"))]
+fn havoc_data(data: &mut [u8]) {
+    unsafe { cvlr_nondet::havoc::memhavoc(data.as_mut_ptr(), data.len()) }
+}
+
+/// Zeroes [data], as `memhavoc` does under `rt` (whose symbol `cvlr-nondet`
+/// does not export)
+#[cfg(feature = "rt")]
+fn havoc_data(data: &mut [u8]) {
+    data.fill(0);
+}
+
+/// Rewrites the lamports and data of the writable accounts of [instruction]
+/// with nondet values. If [resize], their data length changes too.
+fn havoc_writable(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    resize: bool,
+) -> ProgramResult {
+    for meta in instruction.accounts.iter().filter(|meta| meta.is_writable) {
+        let info = account_infos.iter().find(|info| *info.key == meta.pubkey);
+        if let Some(info) = info.filter(|info| info.is_writable) {
+            **info.try_borrow_mut_lamports()? = nondet();
+            if resize {
+                let new_len: usize = nondet();
+                let max_len = unsafe { info.original_data_len() } + MAX_PERMITTED_DATA_INCREASE;
+                cvlr_assume!(new_len <= max_len);
+                info.realloc(new_len, false)?;
+            }
+            havoc_data(&mut info.try_borrow_mut_data()?);
+        }
+    }
+    Ok(())
+}
+
+/// Summary of a CPI into an unknown program
+///
+/// Every account passed as writable may change arbitrarily: its data length
+/// changes within the realloc limits, then its lamports and data are rewritten
+/// with nondet values. The data is havoced at once with `memhavoc`, which
+/// zeroes it under `rt`. Other accounts are unchanged. The callee sets nondet
+/// return data, and the call succeeds or fails with a nondet error.
+///
+/// Writable accounts must be laid out as by the runtime (e.g., by
+/// [`crate::cvlr_deserialize_nondet_accounts`]) since their data is
+/// reallocated.
+pub fn cvlr_havoc_cpi(instruction: &Instruction, account_infos: &[AccountInfo]) -> ProgramResult {
+    havoc_writable(instruction, account_infos, true)?;
+    cvlr_set_nondet_return_data(&instruction.program_id);
+    if nondet() {
+        Ok(())
+    } else {
+        Err(ProgramError::Custom(nondet()))
+    }
+}
+
+/// Returns the account info of the account at [index] in [instruction]
+pub fn cvlr_cpi_account_info<'a, 'b>(
+    instruction: &Instruction,
+    account_infos: &'b [AccountInfo<'a>],
+    index: usize,
+) -> Result<&'b AccountInfo<'a>, ProgramError> {
+    let meta = instruction
+        .accounts
+        .get(index)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    account_infos
+        .iter()
+        .find(|info| *info.key == meta.pubkey)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+/// Model of `invoke_signed`, used by the `invoke!` and `invoke_signed!` macros
+pub fn cvlr_invoke_signed(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    #[allow(static_mut_refs)]
+    unsafe {
+        CVT_CPI_TARGETS.push(instruction.program_id);
+    }
+
+    let checks = unsafe { CVT_CPI_CHECKS };
+
+    if checks.signer_seeds || checks.privileges {
+        let pda_signers = derive_pda_signers(signers_seeds)?;
+        if checks.signer_seeds {
+            check_signers(instruction, account_infos, &pda_signers);
+        }
+        if checks.privileges {
+            check_privileges(instruction, account_infos, &pda_signers);
+        }
+    }
+
+    #[allow(static_mut_refs)]
+    unsafe {
+        let mut executing = CVT_CURRENT_PROGRAM_ID.iter().chain(CVT_INVOKE_STACK.iter());
+        if executing.any(|id| *id == instruction.program_id) {
+            CVT_CPI_SELF_CALL = true;
+        }
+        CVT_INVOKE_STACK.push(instruction.program_id);
+    }
+    cvlr_assert!(
+        cvlr_cpi_depth() <= MAX_CPI_DEPTH,
+        "CallDepth: CPI depth exceeds MAX_CPI_DEPTH"
+    );
+
+    cvlr_reset_return_data(&instruction.program_id);
+    let result = match find_summary(&instruction.program_id) {
+        Some(summary) => summary(instruction, account_infos, signers_seeds),
+        None => cvlr_cpi_default(instruction, account_infos, signers_seeds),
+    };
+
+    #[allow(static_mut_refs)]
+    unsafe {
+        CVT_INVOKE_STACK.pop();
+    }
+    result
+}
@@ -0,0 +1,25 @@
+//! Nondet instruction data
+use cvlr_asserts::cvlr_assume;
+
+use crate::instructions::nondet_bytes;
+
+/// Returns nondet instruction data of nondet length at most [max_len]
+pub fn cvlr_nondet_instruction_data(max_len: usize) -> &'static [u8] {
+    Box::leak(nondet_bytes(max_len).into_boxed_slice())
+}
+
+/// Returns nondet instruction data of at most [max_len] bytes that [decode]
+/// accepts, together with the decoded value
+///
+/// Use it with the decoder of the instruction enum of a program so that its
+/// dispatch code sees every variant, e.g., `|data| T::try_from_slice(data).ok()`
+/// with the version of Borsh the program derives.
+pub fn cvlr_nondet_instruction_data_with<T, F>(max_len: usize, decode: F) -> (&'static [u8], T)
+where
+    F: Fn(&[u8]) -> Option<T>,
+{
+    let data = cvlr_nondet_instruction_data(max_len);
+    let value = decode(data);
+    cvlr_assume!(value.is_some());
+    (data, value.unwrap())
+}
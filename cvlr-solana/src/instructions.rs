@@ -0,0 +1,185 @@
+//! Model of the Instructions sysvar
+//!
+//! The runtime serializes every instruction of the transaction into the data
+//! of the Instructions sysvar account. Programs introspect it with
+//! [`load_current_index_checked`] and [`load_instruction_at_checked`]. The
+//! functions in this module build such an account from a list of (nondet)
+//! instructions, using the same serialization as the runtime.
+//!
+//! [`load_current_index_checked`]: solana_program::sysvar::instructions::load_current_index_checked
+//! [`load_instruction_at_checked`]: solana_program::sysvar::instructions::load_instruction_at_checked
+
+use cvlr_asserts::cvlr_assume;
+use cvlr_nondet::nondet;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    sysvar,
+};
+
+use crate::cvlr_nondet_pubkey;
+
+const IS_SIGNER: u8 = 1 << 0;
+const IS_WRITABLE: u8 = 1 << 1;
+
+/// Returns a vector of nondet bytes of nondet length at most [max_len]
+pub(crate) fn nondet_bytes(max_len: usize) -> Vec<u8> {
+    let len: usize = nondet();
+    cvlr_assume!(len <= max_len);
+    let mut bytes = Vec::with_capacity(len);
+    for _ in 0..len {
+        bytes.push(nondet::<u8>());
+    }
+    bytes
+}
+
+pub fn cvlr_nondet_account_meta() -> AccountMeta {
+    AccountMeta {
+        pubkey: cvlr_nondet_pubkey(),
+        is_signer: nondet(),
+        is_writable: nondet(),
+    }
+}
+
+/// Returns a nondet [`Instruction`] with at most [max_accounts] accounts and
+/// at most [max_data_len] bytes of data
+pub fn cvlr_nondet_instruction(max_accounts: usize, max_data_len: usize) -> Instruction {
+    let num_accounts: usize = nondet();
+    cvlr_assume!(num_accounts <= max_accounts);
+    let mut accounts = Vec::with_capacity(num_accounts);
+    for _ in 0..num_accounts {
+        accounts.push(cvlr_nondet_account_meta());
+    }
+
+    Instruction {
+        program_id: cvlr_nondet_pubkey(),
+        accounts,
+        data: nondet_bytes(max_data_len),
+    }
+}
+
+/// Serializes [instructions] as the runtime does for the data of the
+/// Instructions sysvar, with [current_index] as the index of the executing
+/// instruction
+pub fn cvlr_serialize_instructions_sysvar(
+    instructions: &[Instruction],
+    current_index: u16,
+) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&(instructions.len() as u16).to_le_bytes());
+    // -- offsets of each instruction, filled below
+    data.resize(2 + 2 * instructions.len(), 0);
+
+    for (i, instruction) in instructions.iter().enumerate() {
+        let start = 2 + 2 * i;
+        let offset = data.len() as u16;
+        data[start..start + 2].copy_from_slice(&offset.to_le_bytes());
+
+        data.extend_from_slice(&(instruction.accounts.len() as u16).to_le_bytes());
+        for meta in &instruction.accounts {
+            let mut flags = 0u8;
+            if meta.is_signer {
+                flags |= IS_SIGNER;
+            }
+            if meta.is_writable {
+                flags |= IS_WRITABLE;
+            }
+            data.push(flags);
+            data.extend_from_slice(meta.pubkey.as_ref());
+        }
+        data.extend_from_slice(instruction.program_id.as_ref());
+        data.extend_from_slice(&(instruction.data.len() as u16).to_le_bytes());
+        data.extend_from_slice(&instruction.data);
+    }
+
+    data.extend_from_slice(&current_index.to_le_bytes());
+    data
+}
+
+/// Returns the Instructions sysvar account for [instructions] while
+/// executing the instruction at [current_index]
+pub fn cvlr_instructions_sysvar_account_info(
+    instructions: &[Instruction],
+    current_index: u16,
+) -> AccountInfo<'static> {
+    let data = cvlr_serialize_instructions_sysvar(instructions, current_index);
+    let lamports: &'static mut u64 = Box::leak(Box::new(nondet()));
+    AccountInfo::new(
+        &sysvar::instructions::ID,
+        false,
+        false,
+        lamports,
+        Box::leak(data.into_boxed_slice()),
+        &sysvar::ID,
+        false,
+        nondet(),
+    )
+}
+
+/// Returns the Instructions sysvar account of a transaction with at least one
+/// and at most `N` nondet instructions, executing one of them
+///
+/// Each instruction has at most [max_accounts] accounts and at most
+/// [max_data_len] bytes of data.
+pub fn cvlr_nondet_instructions_sysvar<const N: usize>(
+    max_accounts: usize,
+    max_data_len: usize,
+) -> AccountInfo<'static> {
+    let len: usize = nondet();
+    cvlr_assume!(1 <= len && len <= N);
+    let mut instructions = Vec::with_capacity(len);
+    for _ in 0..len {
+        instructions.push(cvlr_nondet_instruction(max_accounts, max_data_len));
+    }
+
+    let current_index: u16 = nondet();
+    cvlr_assume!((current_index as usize) < len);
+    cvlr_instructions_sysvar_account_info(&instructions, current_index)
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use super::*;
+    use solana_program::{
+        pubkey::Pubkey,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    };
+
+    #[test]
+    fn instructions_sysvar_round_trip() {
+        let instructions = [
+            Instruction {
+                program_id: Pubkey::new_unique(),
+                accounts: vec![
+                    AccountMeta::new(Pubkey::new_unique(), true),
+                    AccountMeta::new(Pubkey::new_unique(), false),
+                    AccountMeta::new_readonly(Pubkey::new_unique(), true),
+                    AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                ],
+                data: vec![1, 2, 3],
+            },
+            Instruction {
+                program_id: Pubkey::new_unique(),
+                accounts: vec![],
+                data: vec![],
+            },
+            Instruction {
+                program_id: Pubkey::new_unique(),
+                accounts: vec![AccountMeta::new(Pubkey::new_unique(), true)],
+                data: (0..=255).collect(),
+            },
+        ];
+
+        for current_index in 0..instructions.len() as u16 {
+            let info = cvlr_instructions_sysvar_account_info(&instructions, current_index);
+            assert_eq!(load_current_index_checked(&info), Ok(current_index));
+            for (index, instruction) in instructions.iter().enumerate() {
+                assert_eq!(
+                    load_instruction_at_checked(index, &info).as_ref(),
+                    Ok(instruction)
+                );
+            }
+            assert!(load_instruction_at_checked(instructions.len(), &info).is_err());
+        }
+    }
+}
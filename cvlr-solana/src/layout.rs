@@ -1,5 +1,53 @@
 use crate::nondet::cvlr_nondet_account_info;
-use solana_program::account_info::AccountInfo;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+/// Largest data length of an account created by [`cvlr_new_account_info`]
+pub const MAX_ORIG_DATA_LEN: usize = 8 * 1024 * 1024;
+
+/// Bounds on the data length of a nondet account
+///
+/// The smaller the bounds, the less memory the prover has to reason about.
+/// For an Anchor account, use `DataSize::exact(8 + T::INIT_SPACE)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DataSize {
+    min: usize,
+    max: usize,
+}
+
+impl DataSize {
+    /// Data length of exactly [len] bytes
+    pub const fn exact(len: usize) -> Self {
+        Self { min: len, max: len }
+    }
+
+    /// Data length in `[min, max]`
+    ///
+    /// Panics if `min > max`, since no data length would satisfy the bounds.
+    pub const fn range(min: usize, max: usize) -> Self {
+        assert!(min <= max, "DataSize::range: min > max");
+        Self { min, max }
+    }
+
+    /// Data length of exactly `size_of::<T>()` bytes
+    pub const fn of<T>() -> Self {
+        Self::exact(std::mem::size_of::<T>())
+    }
+
+    /// Any data length up to [`MAX_ORIG_DATA_LEN`]
+    pub const fn any() -> Self {
+        Self::range(0, MAX_ORIG_DATA_LEN)
+    }
+
+    /// Smallest allowed data length
+    pub const fn min(&self) -> usize {
+        self.min
+    }
+
+    /// Largest allowed data length
+    pub const fn max(&self) -> usize {
+        self.max
+    }
+}
 
 /// Memory layout of AccountInfo field `data` as `Rc<RefCell<&[u8]>>`
 macro_rules! mem_layout_rc_data {
@@ -55,7 +103,43 @@ macro_rules! mem_layout_owner {
     }};
 }
 
-/// The function `fun_acc_infos_with_mem_layout` returns 16 AccountInfo
+/// Maximal data length of each account in [`fun_acc_infos_with_mem_layout_n`]
+///
+/// each account has size of 10MB: 10485760  (0xA00_000)
+const MEM_LAYOUT_DATA_LEN: usize = 10485760;
+
+/// Start addresses of the regions of [`fun_acc_infos_with_mem_layout_sized`]
+struct MemLayout {
+    data: usize,
+    rc_lamports: usize,
+    lamports: usize,
+    rc_data: usize,
+    key: usize,
+    owner: usize,
+}
+
+impl MemLayout {
+    fn new(sizes: &[DataSize]) -> Self {
+        // The start of the context memory region in SVM
+        let input_addr: usize = 0x400_000_000;
+        // the slot of each data has its maximal size. We add 8 just to be conservative.
+        let rc_lamports = input_addr + sizes.iter().map(|size| size.max() + 8).sum::<usize>();
+        let lamports = rc_lamports + sizes.len() * 64;
+        let rc_data = lamports + sizes.len() * 8;
+        let key = rc_data + sizes.len() * 512;
+        let owner = key + sizes.len() * 64;
+        Self {
+            data: input_addr + 8,
+            rc_lamports,
+            lamports,
+            rc_data,
+            key,
+            owner,
+        }
+    }
+}
+
+/// The function `fun_acc_infos_with_mem_layout_n` returns `N` AccountInfo
 /// initialized non-deterministically.
 ///
 /// While the contents of the accounts are unconstrained, this function
@@ -92,23 +176,18 @@ macro_rules! mem_layout_owner {
 ///     pub executable: bool
 /// }
 /// ```
-pub fn fun_acc_infos_with_mem_layout() -> [AccountInfo<'static>; 16] {
-    let acc1 = cvlr_nondet_account_info();
-    let acc2 = cvlr_nondet_account_info();
-    let acc3 = cvlr_nondet_account_info();
-    let acc4 = cvlr_nondet_account_info();
-    let acc5 = cvlr_nondet_account_info();
-    let acc6 = cvlr_nondet_account_info();
-    let acc7 = cvlr_nondet_account_info();
-    let acc8 = cvlr_nondet_account_info();
-    let acc9 = cvlr_nondet_account_info();
-    let acc10 = cvlr_nondet_account_info();
-    let acc11 = cvlr_nondet_account_info();
-    let acc12 = cvlr_nondet_account_info();
-    let acc13 = cvlr_nondet_account_info();
-    let acc14 = cvlr_nondet_account_info();
-    let acc15 = cvlr_nondet_account_info();
-    let acc16 = cvlr_nondet_account_info();
+pub fn fun_acc_infos_with_mem_layout_n<const N: usize>() -> [AccountInfo<'static>; N] {
+    fun_acc_infos_with_mem_layout_sized([DataSize::range(0, MEM_LAYOUT_DATA_LEN); N])
+}
+
+/// [`fun_acc_infos_with_mem_layout_n`] where the data length of each account
+/// is within [sizes]
+///
+/// The data of each account gets a slot of the maximal size of its data.
+pub fn fun_acc_infos_with_mem_layout_sized<const N: usize>(
+    sizes: [DataSize; N],
+) -> [AccountInfo<'static>; N] {
+    let accs: [AccountInfo<'static>; N] = core::array::from_fn(|_| cvlr_nondet_account_info());
 
     /*
      *   When the Solana program entrypoint is called the contents of
@@ -119,6 +198,7 @@ pub fn fun_acc_infos_with_mem_layout() -> [AccountInfo<'static>; 16] {
      *
      *   For convenience, we arrange them differently. First all the data fields, then all the lamport fields, and so on.
      *
+     *   For N = 16:
      *
      *               16                               16                                   16                 16                 16              16
      *   data &[u8] ... data &[u8] | lamports Rc  ... lamports Rc | lamports &u64 ... lamports &u64 | data Rc ... data Rc  | key ... key   | owner ... owner
@@ -128,138 +208,35 @@ pub fn fun_acc_infos_with_mem_layout() -> [AccountInfo<'static>; 16] {
      *   <----  16*0xA00_008 -----><----------  16*64=0x400 ------><------------ 16*8=0x80----------><----16*512=0x2000----><-----0x400---><-----0x400----->
      *
      **/
-    {
-        // layout of data &[u8]
-        // The actual address is 0x400_000_000 and it's the start of the context memory region in SVM
-        let start_addr: u64 = 0x400_000_008;
-        // each account has size of 10MB: 10485760  (0xA00_000). We add 8 just to be conservative.
-        let data_sz: usize = 10485760 + 8;
+    let addrs = MemLayout::new(&sizes);
 
+    if let Some(acc1) = accs.first() {
+        // layout of data &[u8]
         let acc1_data_ptr = acc1.data.borrow().as_ptr();
-        cvlr_asserts::cvlr_assume!(acc1_data_ptr == start_addr as *const u8);
-        mem_layout_data!(acc1, acc2, data_sz);
-        mem_layout_data!(acc2, acc3, data_sz);
-        mem_layout_data!(acc3, acc4, data_sz);
-        mem_layout_data!(acc4, acc5, data_sz);
-        mem_layout_data!(acc5, acc6, data_sz);
-        mem_layout_data!(acc6, acc7, data_sz);
-        mem_layout_data!(acc7, acc8, data_sz);
-        mem_layout_data!(acc8, acc9, data_sz);
-        mem_layout_data!(acc9, acc10, data_sz);
-        mem_layout_data!(acc10, acc11, data_sz);
-        mem_layout_data!(acc11, acc12, data_sz);
-        mem_layout_data!(acc12, acc13, data_sz);
-        mem_layout_data!(acc13, acc14, data_sz);
-        mem_layout_data!(acc14, acc15, data_sz);
-        mem_layout_data!(acc15, acc16, data_sz);
-    }
-    {
-        // layout of lamports Rc<RefCell<T>>
-        let start_addr: usize = 0x40A_000_080;
-        mem_layout_rc_lamport!(acc1, start_addr, 0);
-        mem_layout_rc_lamport!(acc2, start_addr, 1);
-        mem_layout_rc_lamport!(acc3, start_addr, 2);
-        mem_layout_rc_lamport!(acc4, start_addr, 3);
-        mem_layout_rc_lamport!(acc5, start_addr, 4);
-        mem_layout_rc_lamport!(acc6, start_addr, 5);
-        mem_layout_rc_lamport!(acc7, start_addr, 6);
-        mem_layout_rc_lamport!(acc8, start_addr, 7);
-        mem_layout_rc_lamport!(acc9, start_addr, 8);
-        mem_layout_rc_lamport!(acc10, start_addr, 9);
-        mem_layout_rc_lamport!(acc11, start_addr, 10);
-        mem_layout_rc_lamport!(acc12, start_addr, 11);
-        mem_layout_rc_lamport!(acc13, start_addr, 12);
-        mem_layout_rc_lamport!(acc14, start_addr, 13);
-        mem_layout_rc_lamport!(acc15, start_addr, 14);
-        mem_layout_rc_lamport!(acc16, start_addr, 15);
-    }
-    {
+        cvlr_asserts::cvlr_assume!(acc1_data_ptr == addrs.data as *const u8);
         // layout of lamports
-        let start_addr: usize = 0x40A_000_480;
         let acc1_lamports_ptr = *acc1.lamports.borrow() as *const u64;
-        cvlr_asserts::cvlr_assume!(acc1_lamports_ptr == start_addr as *const u64);
-
-        mem_layout_lamport!(acc1, acc2, 8);
-        mem_layout_lamport!(acc2, acc3, 8);
-        mem_layout_lamport!(acc3, acc4, 8);
-        mem_layout_lamport!(acc4, acc5, 8);
-        mem_layout_lamport!(acc5, acc6, 8);
-        mem_layout_lamport!(acc6, acc7, 8);
-        mem_layout_lamport!(acc7, acc8, 8);
-        mem_layout_lamport!(acc8, acc9, 8);
-        mem_layout_lamport!(acc9, acc10, 8);
-        mem_layout_lamport!(acc10, acc11, 8);
-        mem_layout_lamport!(acc11, acc12, 8);
-        mem_layout_lamport!(acc12, acc13, 8);
-        mem_layout_lamport!(acc13, acc14, 8);
-        mem_layout_lamport!(acc14, acc15, 8);
-        mem_layout_lamport!(acc15, acc16, 8);
+        cvlr_asserts::cvlr_assume!(acc1_lamports_ptr == addrs.lamports as *const u64);
     }
-    {
-        // layout of data Rc<RefCell<...>>
-        let start_addr: usize = 0x40A_000_500;
-        mem_layout_rc_data!(acc1, start_addr, 0);
-        mem_layout_rc_data!(acc2, start_addr, 1);
-        mem_layout_rc_data!(acc3, start_addr, 2);
-        mem_layout_rc_data!(acc4, start_addr, 3);
-        mem_layout_rc_data!(acc5, start_addr, 4);
-        mem_layout_rc_data!(acc6, start_addr, 5);
-        mem_layout_rc_data!(acc7, start_addr, 6);
-        mem_layout_rc_data!(acc8, start_addr, 7);
-        mem_layout_rc_data!(acc9, start_addr, 8);
-        mem_layout_rc_data!(acc10, start_addr, 9);
-        mem_layout_rc_data!(acc11, start_addr, 10);
-        mem_layout_rc_data!(acc12, start_addr, 11);
-        mem_layout_rc_data!(acc13, start_addr, 12);
-        mem_layout_rc_data!(acc14, start_addr, 13);
-        mem_layout_rc_data!(acc15, start_addr, 14);
-        mem_layout_rc_data!(acc16, start_addr, 15);
+    for i in 1..N {
+        mem_layout_data!(accs[i - 1], accs[i], sizes[i - 1].max() + 8);
+        mem_layout_lamport!(accs[i - 1], accs[i], 8);
     }
-    {
-        // layout of key
-        let start_addr: usize = 0x40A_002_500;
-        mem_layout_key!(acc1, start_addr, 0);
-        mem_layout_key!(acc2, start_addr, 1);
-        mem_layout_key!(acc3, start_addr, 2);
-        mem_layout_key!(acc4, start_addr, 3);
-        mem_layout_key!(acc5, start_addr, 4);
-        mem_layout_key!(acc6, start_addr, 5);
-        mem_layout_key!(acc7, start_addr, 6);
-        mem_layout_key!(acc8, start_addr, 7);
-        mem_layout_key!(acc9, start_addr, 8);
-        mem_layout_key!(acc10, start_addr, 9);
-        mem_layout_key!(acc11, start_addr, 10);
-        mem_layout_key!(acc12, start_addr, 11);
-        mem_layout_key!(acc13, start_addr, 12);
-        mem_layout_key!(acc14, start_addr, 13);
-        mem_layout_key!(acc15, start_addr, 14);
-        mem_layout_key!(acc16, start_addr, 15);
-    }
-    {
-        // layout of owner
-        let start_addr: usize = 0x40A_002_900;
-        mem_layout_owner!(acc1, start_addr, 0);
-        mem_layout_owner!(acc2, start_addr, 1);
-        mem_layout_owner!(acc3, start_addr, 2);
-        mem_layout_owner!(acc4, start_addr, 3);
-        mem_layout_owner!(acc5, start_addr, 4);
-        mem_layout_owner!(acc6, start_addr, 5);
-        mem_layout_owner!(acc7, start_addr, 6);
-        mem_layout_owner!(acc8, start_addr, 7);
-        mem_layout_owner!(acc9, start_addr, 8);
-        mem_layout_owner!(acc10, start_addr, 9);
-        mem_layout_owner!(acc11, start_addr, 10);
-        mem_layout_owner!(acc12, start_addr, 11);
-        mem_layout_owner!(acc13, start_addr, 12);
-        mem_layout_owner!(acc14, start_addr, 13);
-        mem_layout_owner!(acc15, start_addr, 14);
-        mem_layout_owner!(acc16, start_addr, 15);
+    for (i, acc) in accs.iter().enumerate() {
+        let data_len = acc.data_len();
+        cvlr_asserts::cvlr_assume!(sizes[i].min() <= data_len && data_len <= sizes[i].max());
+        mem_layout_rc_lamport!(acc, addrs.rc_lamports, i);
+        mem_layout_rc_data!(acc, addrs.rc_data, i);
+        mem_layout_key!(acc, addrs.key, i);
+        mem_layout_owner!(acc, addrs.owner, i);
     }
 
-    [
-        acc1, acc2, acc3, acc4, acc5, acc6, acc7, acc8, acc9, acc10, acc11, acc12, acc13, acc14,
-        acc15, acc16,
-    ]
+    accs
+}
+
+/// [`fun_acc_infos_with_mem_layout_n`] for 16 accounts
+pub fn fun_acc_infos_with_mem_layout() -> [AccountInfo<'static>; 16] {
+    fun_acc_infos_with_mem_layout_n::<16>()
 }
 
 #[macro_export]
@@ -270,7 +247,41 @@ macro_rules! acc_infos_with_mem_layout {
 }
 
 pub fn cvlr_new_account_info() -> AccountInfo<'static> {
-    unsafe { cvlr_new_account_info_unchecked() }
+    cvlr_new_account_info_sized(DataSize::any())
+}
+
+/// Returns a nondet account whose data length is within [size]
+pub fn cvlr_new_account_info_sized(size: DataSize) -> AccountInfo<'static> {
+    unsafe { cvlr_new_account_info_unchecked(size) }
+}
+
+/// Checked version of [`AccountInfo::realloc`] for accounts laid out as by
+/// the runtime, such as those of [`cvlr_new_account_info`]
+///
+/// Asserts that the data does not grow by more than
+/// `MAX_PERMITTED_DATA_INCREASE` bytes over its original length, and that
+/// the new bytes are zero if [zero_init] is set.
+pub fn cvlr_realloc(info: &AccountInfo, new_len: usize, zero_init: bool) -> ProgramResult {
+    use cvlr_asserts::cvlr_assert;
+    use solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+    let old_len = info.data_len();
+    let original_data_len = unsafe { info.original_data_len() };
+    cvlr_assert!(
+        new_len.saturating_sub(original_data_len) <= MAX_PERMITTED_DATA_INCREASE,
+        "InvalidRealloc: data grows by more than MAX_PERMITTED_DATA_INCREASE"
+    );
+
+    info.realloc(new_len, zero_init)?;
+    cvlr_assert!(info.data_len() == new_len);
+    if zero_init && new_len > old_len {
+        let data = info.try_borrow_data()?;
+        cvlr_assert!(
+            data[old_len..].iter().all(|b| *b == 0),
+            "realloc did not zero the new bytes"
+        );
+    }
+    Ok(())
 }
 
 mod rt_decls {
@@ -289,8 +300,7 @@ mod rt_impls {
     extern "C" fn CVT_nondet_solana_account_space(size: usize) -> *mut u8 {
         unsafe {
             let layout = Layout::from_size_align_unchecked(size, BPF_ALIGN_OF_U128);
-            let input = alloc_zeroed(layout);
-            input
+            alloc_zeroed(layout)
         }
     }
     #[no_mangle]
@@ -300,7 +310,7 @@ mod rt_impls {
 }
 
 #[allow(unused_assignments)]
-unsafe fn cvlr_new_account_info_unchecked() -> AccountInfo<'static> {
+unsafe fn cvlr_new_account_info_unchecked(size: DataSize) -> AccountInfo<'static> {
     use cvlr_asserts::cvlr_assume;
     use rt_decls::CVT_alloc_slice;
     use solana_program::{
@@ -309,12 +319,12 @@ unsafe fn cvlr_new_account_info_unchecked() -> AccountInfo<'static> {
     };
     use std::{alloc::Layout, cell::RefCell, mem::size_of, rc::Rc};
 
-    const MB: usize = 1024 * 1024;
-    const MAX_ORIG_DATA_LEN: usize = 8 * MB;
-    const SIZE: usize =
-        4 + 4 + 32 + 32 + 8 + 8 + MAX_ORIG_DATA_LEN + MAX_PERMITTED_DATA_INCREASE + 8;
+    let max_data_len = size.max();
+    let total_size: usize = (4 + 4 + 32 + 32 + 8 + 8 + MAX_PERMITTED_DATA_INCREASE + 8)
+        .checked_add(max_data_len)
+        .expect("DataSize::max is too large for an account");
 
-    let layout = Layout::from_size_align_unchecked(SIZE, BPF_ALIGN_OF_U128);
+    let layout = Layout::from_size_align_unchecked(total_size, BPF_ALIGN_OF_U128);
     let input: *mut u8 = rt_decls::CVT_nondet_solana_account_space(layout.size());
 
     let mut offset: usize = 0;
@@ -353,7 +363,7 @@ unsafe fn cvlr_new_account_info_unchecked() -> AccountInfo<'static> {
 
     let data_len: usize = cvlr_nondet::nondet::<usize>();
     // -- limit size of data to what is allocated
-    cvlr_assume!(data_len <= MAX_ORIG_DATA_LEN);
+    cvlr_assume!(size.min() <= data_len && data_len <= max_data_len);
     // -- ensure that original data len is recorded properly
     cvlr_assume!(original_data_len == data_len as u32);
 
@@ -373,7 +383,7 @@ unsafe fn cvlr_new_account_info_unchecked() -> AccountInfo<'static> {
     offset += (offset as *const u8).align_offset(BPF_ALIGN_OF_U128);
 
     // -- place rent_epoch at the end of the data segment
-    offset = SIZE - size_of::<u64>();
+    offset = total_size - size_of::<u64>();
     let rent_epoch = *(input.add(offset) as *const u64);
     cvlr_assume!(cvlr_mathint::is_u64(rent_epoch));
     offset += size_of::<u64>();
@@ -463,23 +473,366 @@ unsafe fn _cvlr_new_account_info_unchecked() -> AccountInfo<'static> {
     }
 }
 
+/// Returns the program id, accounts and instruction data of a nondet input
+/// region with at most `N` accounts, deserialized as by
+/// `solana_program::entrypoint::deserialize`
+///
+/// Accounts may be duplicates of earlier ones, as marked in the input.
+#[allow(clippy::type_complexity)]
+pub fn cvlr_nondet_entrypoint_input<const N: usize>(
+) -> (&'static Pubkey, Vec<AccountInfo<'static>>, &'static [u8]) {
+    unsafe { cvlr_nondet_entrypoint_input_unchecked::<N>() }
+}
+
+// -- instruction data fits in a transaction packet
+const MAX_INSTRUCTION_DATA_LEN: usize = 1232;
+const ACCOUNT_HEADER_SIZE: usize = 1 + 1 + 1 + 1 + 4 + 32 + 32 + 8 + 8;
+
+#[allow(clippy::type_complexity)]
+unsafe fn cvlr_nondet_entrypoint_input_unchecked<const N: usize>(
+) -> (&'static Pubkey, Vec<AccountInfo<'static>>, &'static [u8]) {
+    use solana_program::entrypoint::{BPF_ALIGN_OF_U128, MAX_PERMITTED_DATA_INCREASE};
+    use std::alloc::Layout;
+
+    const MAX_ACCOUNT_SIZE: usize = ACCOUNT_HEADER_SIZE
+        + MAX_ORIG_DATA_LEN
+        + MAX_PERMITTED_DATA_INCREASE
+        + BPF_ALIGN_OF_U128
+        + 8;
+    let size: usize = 8 + N * MAX_ACCOUNT_SIZE + 8 + MAX_INSTRUCTION_DATA_LEN + 32;
+
+    let layout = Layout::from_size_align_unchecked(size, BPF_ALIGN_OF_U128);
+    let input: *mut u8 = rt_decls::CVT_nondet_solana_account_space(layout.size());
+    deserialize_entrypoint_input::<N>(input)
+}
+
+/// Deserializes the input region at [input] with at most `N` accounts, as
+/// `solana_program::entrypoint::deserialize` does
+#[allow(clippy::type_complexity)]
+unsafe fn deserialize_entrypoint_input<const N: usize>(
+    input: *mut u8,
+) -> (&'static Pubkey, Vec<AccountInfo<'static>>, &'static [u8]) {
+    use cvlr_asserts::cvlr_assume;
+    use rt_decls::CVT_alloc_slice;
+    use solana_program::entrypoint::{
+        BPF_ALIGN_OF_U128, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER,
+    };
+    use std::{cell::RefCell, mem::size_of, rc::Rc};
+
+    let mut offset: usize = 0;
+
+    let num_accounts = *(input.add(offset) as *const u64) as usize;
+    cvlr_assume!(num_accounts <= N);
+    offset += size_of::<u64>();
+
+    let mut accounts: Vec<AccountInfo<'static>> = Vec::with_capacity(num_accounts);
+    for i in 0..num_accounts {
+        let header_ptr: *mut u8 = CVT_alloc_slice(input, offset, ACCOUNT_HEADER_SIZE);
+        let dup_info = *header_ptr;
+        if dup_info != NON_DUP_MARKER {
+            cvlr_assume!((dup_info as usize) < i);
+            // -- dup_info and 7 bytes of padding
+            offset += 8;
+            accounts.push(accounts[dup_info as usize].clone());
+            continue;
+        }
+
+        let is_signer = *header_ptr.add(1) != 0;
+        let is_writable = *header_ptr.add(2) != 0;
+        let executable = *header_ptr.add(3) != 0;
+        let original_data_len_ptr = header_ptr.add(4) as *mut u32;
+        let key: &Pubkey = &*(header_ptr.add(8) as *const Pubkey);
+        // -- a non-duplicate is a different account than all earlier ones
+        cvlr_assume!(accounts.iter().all(|a| a.key != key));
+        let owner: &Pubkey = &*(header_ptr.add(40) as *const Pubkey);
+        let lamports_ptr: &mut u64 = &mut *(header_ptr.add(72) as *mut u64);
+        cvlr_assume!(cvlr_mathint::is_u64(*lamports_ptr));
+        let lamports = Rc::new(RefCell::new(lamports_ptr));
+        let data_len = *(header_ptr.add(80) as *const u64) as usize;
+        offset += ACCOUNT_HEADER_SIZE;
+
+        // -- limit size of data to what is allocated
+        cvlr_assume!(data_len <= MAX_ORIG_DATA_LEN);
+        *original_data_len_ptr = data_len as u32;
+
+        let data_ptr: *mut u8 =
+            CVT_alloc_slice(input, offset, data_len + MAX_PERMITTED_DATA_INCREASE);
+        let data = Rc::new(RefCell::new(std::slice::from_raw_parts_mut(
+            data_ptr, data_len,
+        )));
+        offset += data_len + MAX_PERMITTED_DATA_INCREASE;
+        offset += (offset as *const u8).align_offset(BPF_ALIGN_OF_U128);
+
+        let rent_epoch = *(CVT_alloc_slice(input, offset, size_of::<u64>()) as *const u64);
+        cvlr_assume!(cvlr_mathint::is_u64(rent_epoch));
+        offset += size_of::<u64>();
+
+        accounts.push(AccountInfo {
+            key,
+            is_signer,
+            is_writable,
+            lamports,
+            data,
+            owner,
+            executable,
+            rent_epoch,
+        });
+    }
+
+    let instruction_data_len =
+        *(CVT_alloc_slice(input, offset, size_of::<u64>()) as *const u64) as usize;
+    cvlr_assume!(instruction_data_len <= MAX_INSTRUCTION_DATA_LEN);
+    offset += size_of::<u64>();
+
+    let instruction_data: &[u8] = std::slice::from_raw_parts(
+        CVT_alloc_slice(input, offset, instruction_data_len),
+        instruction_data_len,
+    );
+    offset += instruction_data_len;
+
+    let program_id: &Pubkey =
+        &*(CVT_alloc_slice(input, offset, size_of::<Pubkey>()) as *const Pubkey);
+
+    (program_id, accounts, instruction_data)
+}
+
+/// Returns `N` nondet accounts, each in its own account space
+pub fn cvlr_deserialize_nondet_accounts_n<const N: usize>() -> [AccountInfo<'static>; N] {
+    core::array::from_fn(|_| cvlr_new_account_info())
+}
+
+/// Returns `N` nondet accounts, where each account may be a duplicate of an
+/// earlier one
+///
+/// As in the entrypoint serialization, a duplicate is marked by the index of
+/// the account it repeats, and shares its key, flags, lamports and data.
+/// Every other account has a key distinct from all accounts before it.
+pub fn cvlr_deserialize_nondet_accounts_with_dups<const N: usize>() -> [AccountInfo<'static>; N] {
+    use solana_program::entrypoint::NON_DUP_MARKER;
+
+    let mut accs: Vec<AccountInfo<'static>> = Vec::with_capacity(N);
+    for i in 0..N {
+        let dup_info: u8 = cvlr_nondet::nondet();
+        let acc = if dup_info == NON_DUP_MARKER {
+            let acc = cvlr_new_account_info();
+            cvlr_asserts::cvlr_assume!(accs.iter().all(|a| a.key != acc.key));
+            acc
+        } else {
+            cvlr_asserts::cvlr_assume!((dup_info as usize) < i);
+            accs[dup_info as usize].clone()
+        };
+        accs.push(acc);
+    }
+    accs.try_into()
+        .unwrap_or_else(|_| unreachable!("exactly N accounts are deserialized"))
+}
+
+/// Returns `N` nondet accounts whose data lengths are within [sizes]
+pub fn cvlr_deserialize_nondet_accounts_sized<const N: usize>(
+    sizes: [DataSize; N],
+) -> [AccountInfo<'static>; N] {
+    sizes.map(cvlr_new_account_info_sized)
+}
+
+/// [`cvlr_deserialize_nondet_accounts_n`] for 16 accounts
 pub fn cvlr_deserialize_nondet_accounts() -> [AccountInfo<'static>; 16] {
-    [
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-        cvlr_new_account_info(),
-    ]
+    cvlr_deserialize_nondet_accounts_n::<16>()
+}
+
+/// Checks that the layouts assumed in this module match the entrypoint
+/// serialization of the pinned Solana version, and the `Rc` layout of the
+/// Rust standard library
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use super::*;
+    use solana_program::{
+        entrypoint::{deserialize, BPF_ALIGN_OF_U128, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER},
+        program_error::ProgramError,
+        system_instruction::MAX_PERMITTED_DATA_LENGTH,
+    };
+    use std::{cell::RefCell, mem::size_of, rc::Rc};
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+        is_signer: bool,
+        is_writable: bool,
+        executable: bool,
+        rent_epoch: u64,
+    }
+
+    impl TestAccount {
+        fn new(data_len: usize) -> Self {
+            Self {
+                key: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                lamports: 42,
+                data: (0..data_len).map(|i| i as u8).collect(),
+                is_signer: true,
+                is_writable: false,
+                executable: false,
+                rent_epoch: 7,
+            }
+        }
+    }
+
+    /// Serializes [accounts] as the runtime does for the entrypoint, where
+    /// `Err(i)` is a duplicate of the account at index `i`
+    fn serialize(
+        accounts: &[Result<&TestAccount, u8>],
+        instruction_data: &[u8],
+        program_id: &Pubkey,
+    ) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+        for account in accounts {
+            match account {
+                Ok(account) => {
+                    buf.push(NON_DUP_MARKER);
+                    buf.push(account.is_signer as u8);
+                    buf.push(account.is_writable as u8);
+                    buf.push(account.executable as u8);
+                    buf.extend_from_slice(&[0; 4]);
+                    buf.extend_from_slice(account.key.as_ref());
+                    buf.extend_from_slice(account.owner.as_ref());
+                    buf.extend_from_slice(&account.lamports.to_le_bytes());
+                    buf.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+                    buf.extend_from_slice(&account.data);
+                    buf.resize(buf.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+                    buf.resize(buf.len().next_multiple_of(BPF_ALIGN_OF_U128), 0);
+                    buf.extend_from_slice(&account.rent_epoch.to_le_bytes());
+                }
+                Err(index) => {
+                    buf.push(*index);
+                    buf.extend_from_slice(&[0; 7]);
+                }
+            }
+        }
+        buf.extend_from_slice(&(instruction_data.len() as u64).to_le_bytes());
+        buf.extend_from_slice(instruction_data);
+        buf.extend_from_slice(program_id.as_ref());
+        buf
+    }
+
+    /// Copies [bytes] into a leaked buffer aligned like the input region
+    fn aligned(bytes: &[u8]) -> *mut u8 {
+        let words = vec![0u64; bytes.len().div_ceil(8)].leak();
+        let input = words.as_mut_ptr() as *mut u8;
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), input, bytes.len()) };
+        input
+    }
+
+    /// Asserts that [rc] starts 24 bytes before its value, as the
+    /// `mem_layout_rc_*` macros assume, and fits in a slot of [slot_size]
+    fn check_rc<T>(rc: &Rc<RefCell<T>>, slot_size: usize) {
+        let start = unsafe { (rc.as_ptr() as *const u8).offset(-24) } as *const usize;
+        // -- strong and weak counts, then the RefCell
+        assert_eq!(unsafe { *start }, Rc::strong_count(rc));
+        assert_eq!(unsafe { *start.add(1) }, Rc::weak_count(rc) + 1);
+        assert_eq!(unsafe { start.add(2) } as *const RefCell<T>, Rc::as_ptr(rc));
+        assert!(2 * size_of::<usize>() + size_of::<RefCell<T>>() <= slot_size);
+    }
+
+    /// Asserts that the fields of [info] are laid out as by the entrypoint
+    fn check_account_layout(info: &AccountInfo) {
+        let key = info.key as *const Pubkey as usize;
+        let owner = info.owner as *const Pubkey as usize;
+        let lamports = *info.lamports.borrow() as *const u64 as usize;
+        let data = info.data.borrow().as_ptr() as usize;
+        assert_eq!(owner, key + 32);
+        assert_eq!(lamports, owner + 32);
+        assert_eq!(data, lamports + 16);
+        assert_eq!(unsafe { info.original_data_len() }, info.data_len());
+        assert_eq!(
+            unsafe { *((data - 8) as *const u64) } as usize,
+            info.data_len()
+        );
+    }
+
+    /// Asserts that [info] grows by up to `MAX_PERMITTED_DATA_INCREASE` bytes
+    fn check_realloc(info: &AccountInfo) {
+        let max_len = info.data_len() + MAX_PERMITTED_DATA_INCREASE;
+        assert_eq!(
+            info.realloc(max_len + 1, false),
+            Err(ProgramError::InvalidRealloc)
+        );
+        cvlr_realloc(info, max_len, true).unwrap();
+        assert_eq!(info.data_len(), max_len);
+    }
+
+    #[test]
+    fn deserialize_layout() {
+        let (a, b) = (TestAccount::new(10), TestAccount::new(165));
+        let program_id = Pubkey::new_unique();
+        let input = serialize(&[Ok(&a), Ok(&b), Err(0)], &[1, 2, 3], &program_id);
+        let (id, accounts, instruction_data) = unsafe { deserialize(aligned(&input)) };
+
+        assert_eq!(*id, program_id);
+        assert_eq!(instruction_data, &[1, 2, 3]);
+        assert_eq!(accounts.len(), 3);
+        for (info, account) in accounts.iter().zip([&a, &b, &a]) {
+            assert_eq!(*info.key, account.key);
+            assert_eq!(*info.owner, account.owner);
+            assert_eq!(info.lamports(), account.lamports);
+            assert_eq!(*info.data.borrow(), &account.data[..]);
+            assert_eq!(info.rent_epoch, account.rent_epoch);
+            check_account_layout(info);
+            check_rc(&info.lamports, 64);
+            check_rc(&info.data, 512);
+        }
+        assert!(Rc::ptr_eq(&accounts[0].data, &accounts[2].data));
+        check_realloc(&accounts[1]);
+    }
+
+    #[test]
+    fn entrypoint_input_layout() {
+        let (a, b) = (TestAccount::new(10), TestAccount::new(165));
+        let program_id = Pubkey::new_unique();
+        let input = serialize(&[Ok(&a), Ok(&b), Err(0)], &[1, 2, 3], &program_id);
+        let (id, accounts, instruction_data) = unsafe { deserialize(aligned(&input)) };
+        let (cvlr_id, cvlr_accounts, cvlr_instruction_data) =
+            unsafe { deserialize_entrypoint_input::<3>(aligned(&input)) };
+
+        assert_eq!(cvlr_id, id);
+        assert_eq!(cvlr_instruction_data, instruction_data);
+        assert_eq!(cvlr_accounts.len(), accounts.len());
+        for (cvlr_info, info) in cvlr_accounts.iter().zip(&accounts) {
+            assert_eq!(cvlr_info.key, info.key);
+            assert_eq!(cvlr_info.owner, info.owner);
+            assert_eq!(cvlr_info.is_signer, info.is_signer);
+            assert_eq!(cvlr_info.is_writable, info.is_writable);
+            assert_eq!(cvlr_info.executable, info.executable);
+            assert_eq!(cvlr_info.lamports(), info.lamports());
+            assert_eq!(*cvlr_info.data.borrow(), *info.data.borrow());
+            assert_eq!(cvlr_info.rent_epoch, info.rent_epoch);
+            check_account_layout(cvlr_info);
+        }
+        assert!(Rc::ptr_eq(&cvlr_accounts[0].data, &cvlr_accounts[2].data));
+        check_realloc(&cvlr_accounts[1]);
+    }
+
+    #[test]
+    fn new_account_info_layout() {
+        let info = cvlr_new_account_info();
+        check_account_layout(&info);
+        check_rc(&info.lamports, 64);
+        check_rc(&info.data, 512);
+        check_realloc(&info);
+    }
+
+    #[test]
+    fn mem_layout_addresses() {
+        assert_eq!(MEM_LAYOUT_DATA_LEN, MAX_PERMITTED_DATA_LENGTH as usize);
+        assert!(size_of::<Pubkey>() <= 64);
+
+        let addrs = MemLayout::new(&[DataSize::range(0, MEM_LAYOUT_DATA_LEN); 16]);
+        assert_eq!(addrs.data, 0x400_000_008);
+        assert_eq!(addrs.rc_lamports, 0x40A_000_080);
+        assert_eq!(addrs.lamports, 0x40A_000_480);
+        assert_eq!(addrs.rc_data, 0x40A_000_500);
+        assert_eq!(addrs.key, 0x40A_002_500);
+        assert_eq!(addrs.owner, 0x40A_002_900);
+    }
 }
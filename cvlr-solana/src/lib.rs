@@ -1,16 +1,44 @@
+mod account;
 mod clock;
+mod cpi;
+mod instruction_data;
+mod instructions;
 mod layout;
 mod log;
 mod macros;
 mod nondet;
+mod pda;
+pub mod precompiles;
 pub mod pubkey;
+mod return_data;
+#[cfg(any(target_os = "solana", feature = "rt"))]
+mod syscalls;
+pub mod system;
+mod sysvar;
 
 pub mod token;
 
+pub use account::{
+    cvlr_assume_distinct_keys, cvlr_assume_keys_distinct_from,
+    cvlr_deserialize_distinct_nondet_accounts, NondetAccount,
+};
 pub use clock::*;
+pub use cpi::*;
+pub use instruction_data::*;
+pub use instructions::*;
 pub use layout::{
     cvlr_deserialize_nondet_accounts, cvlr_deserialize_nondet_accounts as cvlr_nondet_acc_infos,
-    cvlr_new_account_info, fun_acc_infos_with_mem_layout,
+    cvlr_deserialize_nondet_accounts_n, cvlr_deserialize_nondet_accounts_sized,
+    cvlr_deserialize_nondet_accounts_with_dups, cvlr_new_account_info, cvlr_new_account_info_sized,
+    cvlr_nondet_entrypoint_input, cvlr_realloc, fun_acc_infos_with_mem_layout,
+    fun_acc_infos_with_mem_layout_n, fun_acc_infos_with_mem_layout_sized, DataSize,
+    MAX_ORIG_DATA_LEN,
 };
 pub use log::*;
+pub use macros::*;
 pub use nondet::*;
+pub use pda::*;
+pub use return_data::*;
+#[cfg(feature = "rt")]
+pub use syscalls::cvlr_install_syscall_stubs;
+pub use sysvar::*;
@@ -1,36 +1,204 @@
-// We redefine this macro to avoid including error code conversion/formatting
+use solana_program::program_error::ProgramError;
+
+// We redefine the Anchor require_* family to avoid including error code
+// conversion/formatting. By default, every check is assumed to hold, so the
+// error is never constructed. With the `require-error` feature, a failed
+// check returns its error instead, so that the failing path is verified too.
+// Without an explicit error, the error is the Anchor error code of the macro.
+#[cfg(not(feature = "require-error"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cvlr_require {
+    ($invariant: expr, $error: expr) => {
+        cvt::CVT_assume($invariant);
+    };
+}
+
+#[cfg(feature = "require-error")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cvlr_require {
+    ($invariant: expr, $error: expr) => {
+        if !($invariant) {
+            return ::core::result::Result::Err(::core::convert::Into::into($error));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! require {
+    ($invariant: expr, $error: expr $(,)?) => {
+        $crate::__cvlr_require!($invariant, $error)
+    };
+}
+
+#[macro_export]
+macro_rules! require_eq {
+    ($value1: expr, $value2: expr, $error_code: expr $(,)?) => {
+        $crate::__cvlr_require!($value1 == $value2, $error_code)
+    };
+    ($value1: expr, $value2: expr $(,)?) => {
+        $crate::__cvlr_require!(
+            $value1 == $value2,
+            $crate::cvlr_require_error($crate::REQUIRE_EQ_VIOLATED)
+        )
+    };
+}
+
+#[macro_export]
+macro_rules! require_neq {
+    ($value1: expr, $value2: expr, $error_code: expr $(,)?) => {
+        $crate::__cvlr_require!($value1 != $value2, $error_code)
+    };
+    ($value1: expr, $value2: expr $(,)?) => {
+        $crate::__cvlr_require!(
+            $value1 != $value2,
+            $crate::cvlr_require_error($crate::REQUIRE_NEQ_VIOLATED)
+        )
+    };
+}
+
 #[macro_export]
 macro_rules! require_keys_eq {
     ($value1: expr, $value2: expr, $error_code:expr $(,)?) => {
-        cvt::CVT_assume($value1 == $value2);
+        $crate::__cvlr_require!($value1 == $value2, $error_code)
     };
     ($value1: expr, $value2: expr $(,)?) => {
-        cvt::CVT_assume($value1 == $value2);
+        $crate::__cvlr_require!(
+            $value1 == $value2,
+            $crate::cvlr_require_error($crate::REQUIRE_KEYS_EQ_VIOLATED)
+        )
     };
 }
 
 #[macro_export]
 macro_rules! require_keys_neq {
     ($value1: expr, $value2: expr, $error_code:expr $(,)?) => {
-        cvt::CVT_assume($value1 != $value2);
+        $crate::__cvlr_require!($value1 != $value2, $error_code)
+    };
+    ($value1: expr, $value2: expr $(,)?) => {
+        $crate::__cvlr_require!(
+            $value1 != $value2,
+            $crate::cvlr_require_error($crate::REQUIRE_KEYS_NEQ_VIOLATED)
+        )
+    };
+}
+
+#[macro_export]
+macro_rules! require_gt {
+    ($value1: expr, $value2: expr, $error_code: expr $(,)?) => {
+        $crate::__cvlr_require!($value1 > $value2, $error_code)
+    };
+    ($value1: expr, $value2: expr $(,)?) => {
+        $crate::__cvlr_require!(
+            $value1 > $value2,
+            $crate::cvlr_require_error($crate::REQUIRE_GT_VIOLATED)
+        )
+    };
+}
+
+#[macro_export]
+macro_rules! require_gte {
+    ($value1: expr, $value2: expr, $error_code: expr $(,)?) => {
+        $crate::__cvlr_require!($value1 >= $value2, $error_code)
     };
     ($value1: expr, $value2: expr $(,)?) => {
-        cvt::CVT_assume($value1 != $value2);
+        $crate::__cvlr_require!(
+            $value1 >= $value2,
+            $crate::cvlr_require_error($crate::REQUIRE_GTE_VIOLATED)
+        )
+    };
+}
+
+/// Anchor error code of a failed `require!`
+pub const REQUIRE_VIOLATED: u32 = 2500;
+/// Anchor error code of a failed `require_eq!`
+pub const REQUIRE_EQ_VIOLATED: u32 = 2501;
+/// Anchor error code of a failed `require_keys_eq!`
+pub const REQUIRE_KEYS_EQ_VIOLATED: u32 = 2502;
+/// Anchor error code of a failed `require_neq!`
+pub const REQUIRE_NEQ_VIOLATED: u32 = 2503;
+/// Anchor error code of a failed `require_keys_neq!`
+pub const REQUIRE_KEYS_NEQ_VIOLATED: u32 = 2504;
+/// Anchor error code of a failed `require_gt!`
+pub const REQUIRE_GT_VIOLATED: u32 = 2505;
+/// Anchor error code of a failed `require_gte!`
+pub const REQUIRE_GTE_VIOLATED: u32 = 2506;
+
+/// Returns the error of a failed require_* macro with Anchor error [code]
+#[doc(hidden)]
+pub fn cvlr_require_error(code: u32) -> ProgramError {
+    ProgramError::Custom(code)
+}
+
+// Unlike Anchor, errors do not record their source location, and error!
+// returns a ProgramError, which converts to the error type of the program
+// with `?` or `.into()`
+#[macro_export]
+macro_rules! error {
+    ($error: expr $(,)?) => {
+        $crate::cvlr_error($error)
     };
 }
 
+#[macro_export]
+macro_rules! err {
+    ($error: expr $(,)?) => {
+        ::core::result::Result::Err(::core::convert::Into::into($crate::error!($error)))
+    };
+}
+
+/// Returns [error] as a `ProgramError`, as built by `error!`
+#[doc(hidden)]
+pub fn cvlr_error<E: Into<ProgramError>>(error: E) -> ProgramError {
+    error.into()
+}
+
 // We redefine invoke and invoke_signed to avoid the signer checks
+// The signer and privilege checks can be re-enabled with `cvlr_cpi_set_checks`
 // TODO: we might want to move these macros to mocks
 #[macro_export]
 macro_rules! invoke {
     ($instruction: expr, $acc_infos: expr $(,)*) => {{
-        solana_program::program::invoke_signed_unchecked($instruction, $acc_infos, &[])
+        $crate::cvlr_invoke_signed($instruction, $acc_infos, &[])
     }};
 }
 
 #[macro_export]
 macro_rules! invoke_signed {
     ($instruction: expr, $acc_infos: expr, $seeds: expr $(,)*) => {{
-        solana_program::program::invoke_signed_unchecked($instruction, $acc_infos, $seeds)
+        $crate::cvlr_invoke_signed($instruction, $acc_infos, $seeds)
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use solana_program::program_error::ProgramError;
+
+    enum TestError {
+        Missing,
+    }
+
+    impl From<TestError> for ProgramError {
+        fn from(error: TestError) -> Self {
+            match error {
+                TestError::Missing => ProgramError::Custom(6000),
+            }
+        }
+    }
+
+    fn get(value: Option<u8>) -> Result<u8, ProgramError> {
+        let value = value.ok_or(error!(TestError::Missing))?;
+        if value == 0 {
+            return err!(TestError::Missing);
+        }
+        Ok(value)
+    }
+
+    #[test]
+    fn error_macros() {
+        assert_eq!(get(Some(1)), Ok(1));
+        assert_eq!(get(None), Err(ProgramError::Custom(6000)));
+        assert_eq!(get(Some(0)), Err(ProgramError::Custom(6000)));
+    }
+}
@@ -1,4 +1,6 @@
-use solana_program::{account_info::AccountInfo, program_option::COption, pubkey::Pubkey};
+use solana_program::{
+    account_info::AccountInfo, hash::Hash, program_option::COption, pubkey::Pubkey,
+};
 
 mod rt_decls {
     use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
@@ -19,7 +21,7 @@ mod rt_impls {
     #[no_mangle]
     extern "C" fn CVT_nondet_account_info() -> AccountInfo<'static> {
         let owner: &'static mut _ = Box::leak(Box::new(Pubkey::new_unique()));
-        let acc = Account::new(1, 2, &owner);
+        let acc = Account::new(1, 2, owner);
         let pk: &'static mut _ = Box::leak(Box::new(Pubkey::new_unique()));
         let v: &'static mut _ = Box::leak(Box::new((*pk, acc)));
         v.into()
@@ -60,3 +62,11 @@ where
 pub fn cvlr_nondet_coption_pubkey() -> COption<Pubkey> {
     cvlr_nondet_coption(cvlr_nondet_pubkey)
 }
+
+pub fn cvlr_nondet_hash() -> Hash {
+    let mut buf = [0u8; 32];
+    for word in buf.chunks_exact_mut(8) {
+        word.copy_from_slice(&cvlr_nondet::nondet::<u64>().to_le_bytes());
+    }
+    Hash::new_from_array(buf)
+}
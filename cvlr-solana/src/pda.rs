@@ -0,0 +1,218 @@
+//! Abstract model of program derived addresses (PDA)
+//!
+//! The runtime derives a PDA by hashing the seeds and the program id with
+//! SHA-256, which the prover cannot reason about. Here, derivation is modelled
+//! as an uninterpreted injective function of the concatenated seeds and the
+//! program id:
+//!
+//!   - it is deterministic: deriving twice from the same seeds and program id
+//!     gives the same address (or the same failure)
+//!   - it is injective: different seeds or program ids give different
+//!     addresses
+//!   - derived addresses are never on the ed25519 curve
+//!   - the canonical bump found by `find_program_address` is nondet, but the
+//!     same for every lookup with the same seeds and program id, and no
+//!     larger bump derives a valid address
+//!
+//! Like the runtime, seeds are hashed by concatenation, so `["ab", "c"]` and
+//! `["a", "bc"]` derive the same address.
+//!
+//! When compiled for the SVM, `Pubkey::create_program_address` and
+//! `Pubkey::find_program_address` use this model through their syscalls. With
+//! the `rt` feature, they keep computing the real addresses, and so does the
+//! model, since nondet keys are not distinct under `rt`.
+
+use cvlr_asserts::cvlr_assume;
+use cvlr_nondet::nondet;
+use solana_program::pubkey::{Pubkey, PubkeyError, MAX_SEEDS, MAX_SEED_LEN};
+
+struct Derivation {
+    seeds: Vec<u8>,
+    program_id: Pubkey,
+    /// `None` if the hash is on the curve and thus not a valid PDA
+    address: Option<Pubkey>,
+}
+
+struct CanonicalBump {
+    seeds: Vec<u8>,
+    program_id: Pubkey,
+    bump: u8,
+}
+
+static mut CVT_PDA_DERIVATIONS: Vec<Derivation> = Vec::new();
+static mut CVT_PDA_CANONICAL_BUMPS: Vec<CanonicalBump> = Vec::new();
+
+fn concat_seeds(seeds: &[&[u8]]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(seeds.len() * MAX_SEED_LEN);
+    for seed in seeds {
+        buf.extend_from_slice(seed);
+    }
+    buf
+}
+
+fn check_seeds(seeds: &[&[u8]]) -> Result<(), PubkeyError> {
+    if seeds.len() > MAX_SEEDS || seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+        return Err(PubkeyError::MaxSeedLengthExceeded);
+    }
+    Ok(())
+}
+
+/// Returns the address derived from [seeds] and [program_id], or `None` if
+/// it falls on the curve. If [must_be_valid], it is assumed not to.
+#[allow(static_mut_refs)]
+fn derive(seeds: Vec<u8>, program_id: &Pubkey, must_be_valid: bool) -> Option<Pubkey> {
+    let derivations = unsafe { &mut CVT_PDA_DERIVATIONS };
+
+    if let Some(d) = derivations
+        .iter()
+        .find(|d| d.program_id == *program_id && d.seeds == seeds)
+    {
+        if must_be_valid {
+            cvlr_assume!(d.address.is_some());
+        }
+        return d.address;
+    }
+
+    // -- bumps above the canonical bump derive no valid address
+    let above_canonical_bump = seeds.split_last().is_some_and(|(bump, prefix)| {
+        unsafe { CVT_PDA_CANONICAL_BUMPS.iter() }
+            .any(|b| b.program_id == *program_id && b.seeds == prefix && b.bump < *bump)
+    });
+    let address = if above_canonical_bump {
+        None
+    } else {
+        new_address(derivations, &seeds, program_id, must_be_valid)
+    };
+
+    derivations.push(Derivation {
+        seeds,
+        program_id: *program_id,
+        address,
+    });
+    address
+}
+
+/// Returns a new address, different from every address in [derivations], or
+/// `None` if it falls on the curve. If [must_be_valid], it is assumed not to.
+#[cfg(not(feature = "rt"))]
+fn new_address(
+    derivations: &[Derivation],
+    _seeds: &[u8],
+    _program_id: &Pubkey,
+    must_be_valid: bool,
+) -> Option<Pubkey> {
+    if !must_be_valid && nondet::<bool>() {
+        return None;
+    }
+    let address = crate::cvlr_nondet_pubkey();
+    // -- injectivity
+    for d in derivations.iter() {
+        if let Some(other) = d.address {
+            cvlr_assume!(address != other);
+        }
+    }
+    Some(address)
+}
+
+/// Returns the address derived by the runtime, since nondet keys are not
+/// distinct under `rt`
+#[cfg(feature = "rt")]
+fn new_address(
+    _derivations: &[Derivation],
+    seeds: &[u8],
+    program_id: &Pubkey,
+    _must_be_valid: bool,
+) -> Option<Pubkey> {
+    let seeds: Vec<&[u8]> = seeds.chunks(MAX_SEED_LEN).collect();
+    Pubkey::create_program_address(&seeds, program_id).ok()
+}
+
+/// Returns a new canonical bump for [seeds] and [program_id], not below any
+/// bump that already derived a valid address
+#[cfg(not(feature = "rt"))]
+#[allow(static_mut_refs)]
+fn new_canonical_bump(seeds: &[u8], program_id: &Pubkey) -> Option<u8> {
+    let bump: u8 = nondet();
+    for d in unsafe { CVT_PDA_DERIVATIONS.iter() } {
+        if let Some((other, prefix)) = d.seeds.split_last() {
+            if d.program_id == *program_id && prefix == seeds && d.address.is_some() {
+                cvlr_assume!(bump >= *other);
+            }
+        }
+    }
+    Some(bump)
+}
+
+/// Returns the canonical bump found by the runtime
+#[cfg(feature = "rt")]
+fn new_canonical_bump(seeds: &[u8], program_id: &Pubkey) -> Option<u8> {
+    let seeds: Vec<&[u8]> = seeds.chunks(MAX_SEED_LEN).collect();
+    Pubkey::try_find_program_address(&seeds, program_id).map(|(_, bump)| bump)
+}
+
+/// Model of [`Pubkey::create_program_address`]
+pub fn cvlr_create_program_address(
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> Result<Pubkey, PubkeyError> {
+    check_seeds(seeds)?;
+    derive(concat_seeds(seeds), program_id, false).ok_or(PubkeyError::InvalidSeeds)
+}
+
+/// Model of [`Pubkey::try_find_program_address`]
+#[allow(static_mut_refs)]
+pub fn cvlr_try_find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+    // -- one seed is taken by the bump
+    if seeds.len() >= MAX_SEEDS || check_seeds(seeds).is_err() {
+        return None;
+    }
+
+    let seeds = concat_seeds(seeds);
+    let bumps = unsafe { &mut CVT_PDA_CANONICAL_BUMPS };
+    let bump = match bumps
+        .iter()
+        .find(|b| b.program_id == *program_id && b.seeds == seeds)
+    {
+        Some(b) => b.bump,
+        None => {
+            let bump = new_canonical_bump(&seeds, program_id)?;
+            bumps.push(CanonicalBump {
+                seeds: seeds.clone(),
+                program_id: *program_id,
+                bump,
+            });
+            bump
+        }
+    };
+
+    let mut seeds_with_bump = seeds;
+    seeds_with_bump.push(bump);
+    derive(seeds_with_bump, program_id, true).map(|address| (address, bump))
+}
+
+/// Model of [`Pubkey::find_program_address`]
+pub fn cvlr_find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+    cvlr_try_find_program_address(seeds, program_id)
+        .expect("Unable to find a viable program address bump seed")
+}
+
+/// Returns true if [key] was derived as a PDA in this run
+#[allow(static_mut_refs)]
+pub fn cvlr_is_program_address(key: &Pubkey) -> bool {
+    unsafe { CVT_PDA_DERIVATIONS.iter() }.any(|d| d.address.as_ref() == Some(key))
+}
+
+/// Model of [`Pubkey::is_on_curve`]: PDAs are never on the curve, any other
+/// key might be
+pub fn cvlr_is_on_curve(key: &Pubkey) -> bool {
+    !cvlr_is_program_address(key) && nondet()
+}
+
+/// Forgets all PDA derivations of this run
+#[allow(static_mut_refs)]
+pub fn cvlr_pda_reset() {
+    unsafe {
+        CVT_PDA_DERIVATIONS.clear();
+        CVT_PDA_CANONICAL_BUMPS.clear();
+    }
+}
@@ -0,0 +1,412 @@
+//! Models of the Ed25519 and secp256k1 signature verification precompiles
+//!
+//! The runtime rejects a transaction if one of its precompile instructions
+//! fails to verify. Hence, a precompile instruction found in the Instructions
+//! sysvar stands for a valid signature. The functions in this module build
+//! such instructions with nondet signature bytes, and parse their offsets the
+//! way programs do.
+
+use cvlr_asserts::cvlr_assume;
+use cvlr_nondet::nondet;
+use solana_program::{
+    ed25519_program, instruction::Instruction, pubkey::Pubkey, secp256k1_program,
+};
+
+use crate::{cvlr_nondet_pubkey, instructions::nondet_bytes};
+
+pub const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+
+pub const ED25519_PUBKEY_SERIALIZED_SIZE: usize = 32;
+pub const ED25519_SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+pub const ED25519_SIGNATURE_OFFSETS_START: usize = 2;
+/// Instruction index referring to the Ed25519Program instruction itself
+pub const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+pub const SECP256K1_ETH_ADDRESS_SERIALIZED_SIZE: usize = 20;
+pub const SECP256K1_SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 11;
+pub const SECP256K1_SIGNATURE_OFFSETS_START: usize = 1;
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn nondet_signature() -> [u8; SIGNATURE_SERIALIZED_SIZE] {
+    let mut signature = [0u8; SIGNATURE_SERIALIZED_SIZE];
+    for b in signature.iter_mut() {
+        *b = nondet();
+    }
+    signature
+}
+
+/// Offsets of one signature in the data of an Ed25519Program instruction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ed25519SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u16,
+    pub public_key_offset: u16,
+    pub public_key_instruction_index: u16,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u16,
+}
+
+impl Ed25519SignatureOffsets {
+    /// Unpacks the offsets of the [index]-th signature of instruction [data]
+    pub fn unpack(data: &[u8], index: usize) -> Option<Self> {
+        let num_signatures = *data.first()? as usize;
+        if index >= num_signatures {
+            return None;
+        }
+        let start =
+            ED25519_SIGNATURE_OFFSETS_START + index * ED25519_SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        Some(Self {
+            signature_offset: read_u16(data, start)?,
+            signature_instruction_index: read_u16(data, start + 2)?,
+            public_key_offset: read_u16(data, start + 4)?,
+            public_key_instruction_index: read_u16(data, start + 6)?,
+            message_data_offset: read_u16(data, start + 8)?,
+            message_data_size: read_u16(data, start + 10)?,
+            message_instruction_index: read_u16(data, start + 12)?,
+        })
+    }
+
+    fn pack_into(&self, dst: &mut Vec<u8>) {
+        dst.extend_from_slice(&self.signature_offset.to_le_bytes());
+        dst.extend_from_slice(&self.signature_instruction_index.to_le_bytes());
+        dst.extend_from_slice(&self.public_key_offset.to_le_bytes());
+        dst.extend_from_slice(&self.public_key_instruction_index.to_le_bytes());
+        dst.extend_from_slice(&self.message_data_offset.to_le_bytes());
+        dst.extend_from_slice(&self.message_data_size.to_le_bytes());
+        dst.extend_from_slice(&self.message_instruction_index.to_le_bytes());
+    }
+}
+
+/// Offsets of one signature in the data of a Secp256k1Program instruction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Secp256k1SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u8,
+    pub eth_address_offset: u16,
+    pub eth_address_instruction_index: u8,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u8,
+}
+
+impl Secp256k1SignatureOffsets {
+    /// Unpacks the offsets of the [index]-th signature of instruction [data]
+    pub fn unpack(data: &[u8], index: usize) -> Option<Self> {
+        let num_signatures = *data.first()? as usize;
+        if index >= num_signatures {
+            return None;
+        }
+        let start =
+            SECP256K1_SIGNATURE_OFFSETS_START + index * SECP256K1_SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        let chunk = data.get(start..start + SECP256K1_SIGNATURE_OFFSETS_SERIALIZED_SIZE)?;
+        Some(Self {
+            signature_offset: read_u16(chunk, 0)?,
+            signature_instruction_index: chunk[2],
+            eth_address_offset: read_u16(chunk, 3)?,
+            eth_address_instruction_index: chunk[5],
+            message_data_offset: read_u16(chunk, 6)?,
+            message_data_size: read_u16(chunk, 8)?,
+            message_instruction_index: chunk[10],
+        })
+    }
+
+    fn pack_into(&self, dst: &mut Vec<u8>) {
+        dst.extend_from_slice(&self.signature_offset.to_le_bytes());
+        dst.push(self.signature_instruction_index);
+        dst.extend_from_slice(&self.eth_address_offset.to_le_bytes());
+        dst.push(self.eth_address_instruction_index);
+        dst.extend_from_slice(&self.message_data_offset.to_le_bytes());
+        dst.extend_from_slice(&self.message_data_size.to_le_bytes());
+        dst.push(self.message_instruction_index);
+    }
+}
+
+/// Returns an Ed25519Program instruction verifying a signature of [message]
+/// by [pubkey]
+///
+/// The layout is the one of `new_ed25519_instruction` from `solana-sdk`. The
+/// signature bytes are nondet: the instruction being in the transaction is
+/// what makes the signature valid.
+pub fn cvlr_ed25519_instruction(pubkey: &Pubkey, message: &[u8]) -> Instruction {
+    cvlr_assume!(message.len() <= u16::MAX as usize);
+
+    let public_key_offset =
+        ED25519_SIGNATURE_OFFSETS_START + ED25519_SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+    let signature_offset = public_key_offset + ED25519_PUBKEY_SERIALIZED_SIZE;
+    let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+    let offsets = Ed25519SignatureOffsets {
+        signature_offset: signature_offset as u16,
+        signature_instruction_index: ED25519_CURRENT_INSTRUCTION,
+        public_key_offset: public_key_offset as u16,
+        public_key_instruction_index: ED25519_CURRENT_INSTRUCTION,
+        message_data_offset: message_data_offset as u16,
+        message_data_size: message.len() as u16,
+        message_instruction_index: ED25519_CURRENT_INSTRUCTION,
+    };
+
+    let mut data = Vec::with_capacity(message_data_offset + message.len());
+    data.extend_from_slice(&[1, 0]);
+    offsets.pack_into(&mut data);
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(&nondet_signature());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Returns an Ed25519Program instruction verifying a signature by a nondet
+/// public key of a nondet message of at most [max_message_len] bytes
+pub fn cvlr_nondet_ed25519_instruction(max_message_len: usize) -> Instruction {
+    cvlr_ed25519_instruction(&cvlr_nondet_pubkey(), &nondet_bytes(max_message_len))
+}
+
+/// Returns the public key and message of the [index]-th signature verified by
+/// Ed25519Program [instruction]
+///
+/// Returns `None` if [instruction] is not an Ed25519Program instruction, or
+/// if the public key or the message are not stored in [instruction] itself.
+pub fn cvlr_ed25519_signed_message(
+    instruction: &Instruction,
+    index: usize,
+) -> Option<(Pubkey, &[u8])> {
+    if !ed25519_program::check_id(&instruction.program_id) {
+        return None;
+    }
+    let data = &instruction.data;
+    let offsets = Ed25519SignatureOffsets::unpack(data, index)?;
+    if offsets.public_key_instruction_index != ED25519_CURRENT_INSTRUCTION
+        || offsets.message_instruction_index != ED25519_CURRENT_INSTRUCTION
+    {
+        return None;
+    }
+
+    let pk_start = offsets.public_key_offset as usize;
+    let pubkey = data.get(pk_start..pk_start + ED25519_PUBKEY_SERIALIZED_SIZE)?;
+    let msg_start = offsets.message_data_offset as usize;
+    let message = data.get(msg_start..msg_start + offsets.message_data_size as usize)?;
+    Some((Pubkey::try_from(pubkey).ok()?, message))
+}
+
+/// Returns a Secp256k1Program instruction verifying a signature of [message]
+/// by the key of [eth_address]
+///
+/// [instruction_index] is the index of the instruction in the transaction.
+/// The layout is the one of `new_secp256k1_instruction` from `solana-sdk`.
+/// The signature and recovery id are nondet: the instruction being in the
+/// transaction is what makes the signature valid.
+pub fn cvlr_secp256k1_instruction(
+    eth_address: &[u8; SECP256K1_ETH_ADDRESS_SERIALIZED_SIZE],
+    message: &[u8],
+    instruction_index: u8,
+) -> Instruction {
+    cvlr_assume!(message.len() <= u16::MAX as usize);
+
+    let eth_address_offset =
+        SECP256K1_SIGNATURE_OFFSETS_START + SECP256K1_SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+    let signature_offset = eth_address_offset + SECP256K1_ETH_ADDRESS_SERIALIZED_SIZE;
+    // -- the signature is followed by a 1-byte recovery id
+    let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE + 1;
+    let offsets = Secp256k1SignatureOffsets {
+        signature_offset: signature_offset as u16,
+        signature_instruction_index: instruction_index,
+        eth_address_offset: eth_address_offset as u16,
+        eth_address_instruction_index: instruction_index,
+        message_data_offset: message_data_offset as u16,
+        message_data_size: message.len() as u16,
+        message_instruction_index: instruction_index,
+    };
+
+    let mut data = Vec::with_capacity(message_data_offset + message.len());
+    data.push(1);
+    offsets.pack_into(&mut data);
+    data.extend_from_slice(eth_address);
+    data.extend_from_slice(&nondet_signature());
+    data.push(nondet());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: secp256k1_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Returns a Secp256k1Program instruction verifying a signature by a nondet
+/// Ethereum address of a nondet message of at most [max_message_len] bytes
+pub fn cvlr_nondet_secp256k1_instruction(
+    max_message_len: usize,
+    instruction_index: u8,
+) -> Instruction {
+    let mut eth_address = [0u8; SECP256K1_ETH_ADDRESS_SERIALIZED_SIZE];
+    for b in eth_address.iter_mut() {
+        *b = nondet();
+    }
+    cvlr_secp256k1_instruction(
+        &eth_address,
+        &nondet_bytes(max_message_len),
+        instruction_index,
+    )
+}
+
+/// Returns the Ethereum address and message of the [index]-th signature
+/// verified by Secp256k1Program [instruction], found at [instruction_index]
+/// in the transaction
+///
+/// Returns `None` if [instruction] is not a Secp256k1Program instruction, or
+/// if the address or the message are not stored in [instruction] itself.
+pub fn cvlr_secp256k1_signed_message(
+    instruction: &Instruction,
+    index: usize,
+    instruction_index: u8,
+) -> Option<([u8; SECP256K1_ETH_ADDRESS_SERIALIZED_SIZE], &[u8])> {
+    if !secp256k1_program::check_id(&instruction.program_id) {
+        return None;
+    }
+    let data = &instruction.data;
+    let offsets = Secp256k1SignatureOffsets::unpack(data, index)?;
+    if offsets.eth_address_instruction_index != instruction_index
+        || offsets.message_instruction_index != instruction_index
+    {
+        return None;
+    }
+
+    let addr_start = offsets.eth_address_offset as usize;
+    let eth_address = data.get(addr_start..addr_start + SECP256K1_ETH_ADDRESS_SERIALIZED_SIZE)?;
+    let msg_start = offsets.message_data_offset as usize;
+    let message = data.get(msg_start..msg_start + offsets.message_data_size as usize)?;
+    Some((eth_address.try_into().ok()?, message))
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        ed25519_instruction, feature_set::FeatureSet, precompiles::PrecompileError,
+        secp256k1_instruction,
+    };
+
+    #[test]
+    fn ed25519_layout() {
+        assert_eq!(
+            ED25519_PUBKEY_SERIALIZED_SIZE,
+            ed25519_instruction::PUBKEY_SERIALIZED_SIZE
+        );
+        assert_eq!(
+            SIGNATURE_SERIALIZED_SIZE,
+            ed25519_instruction::SIGNATURE_SERIALIZED_SIZE
+        );
+        assert_eq!(
+            ED25519_SIGNATURE_OFFSETS_SERIALIZED_SIZE,
+            ed25519_instruction::SIGNATURE_OFFSETS_SERIALIZED_SIZE
+        );
+        assert_eq!(
+            ED25519_SIGNATURE_OFFSETS_START,
+            ed25519_instruction::SIGNATURE_OFFSETS_START
+        );
+
+        let pubkey = Pubkey::new_unique();
+        let message = b"cvlr";
+        let instruction = cvlr_ed25519_instruction(&pubkey, message);
+        let data = &instruction.data;
+
+        // -- as laid out by `new_ed25519_instruction`
+        let public_key_offset = ed25519_instruction::DATA_START;
+        let signature_offset = public_key_offset + ed25519_instruction::PUBKEY_SERIALIZED_SIZE;
+        let message_data_offset = signature_offset + ed25519_instruction::SIGNATURE_SERIALIZED_SIZE;
+        assert_eq!(data.len(), message_data_offset + message.len());
+        assert_eq!(
+            Ed25519SignatureOffsets::unpack(data, 0),
+            Some(Ed25519SignatureOffsets {
+                signature_offset: signature_offset as u16,
+                signature_instruction_index: u16::MAX,
+                public_key_offset: public_key_offset as u16,
+                public_key_instruction_index: u16::MAX,
+                message_data_offset: message_data_offset as u16,
+                message_data_size: message.len() as u16,
+                message_instruction_index: u16::MAX,
+            })
+        );
+        assert_eq!(Ed25519SignatureOffsets::unpack(data, 1), None);
+        assert_eq!(
+            cvlr_ed25519_signed_message(&instruction, 0),
+            Some((pubkey, &message[..]))
+        );
+
+        // -- the precompile finds the signature, public key and message, and
+        // -- only rejects the nondet signature or key
+        let result = ed25519_instruction::verify(data, &[data], &FeatureSet::all_enabled());
+        assert!(matches!(
+            result,
+            Err(PrecompileError::InvalidSignature | PrecompileError::InvalidPublicKey)
+        ));
+    }
+
+    #[test]
+    fn secp256k1_layout() {
+        assert_eq!(
+            SECP256K1_ETH_ADDRESS_SERIALIZED_SIZE,
+            secp256k1_instruction::HASHED_PUBKEY_SERIALIZED_SIZE
+        );
+        assert_eq!(
+            SIGNATURE_SERIALIZED_SIZE,
+            secp256k1_instruction::SIGNATURE_SERIALIZED_SIZE
+        );
+        assert_eq!(
+            SECP256K1_SIGNATURE_OFFSETS_SERIALIZED_SIZE,
+            secp256k1_instruction::SIGNATURE_OFFSETS_SERIALIZED_SIZE
+        );
+        assert_eq!(
+            SECP256K1_SIGNATURE_OFFSETS_START + SECP256K1_SIGNATURE_OFFSETS_SERIALIZED_SIZE,
+            secp256k1_instruction::DATA_START
+        );
+
+        let eth_address = [7u8; SECP256K1_ETH_ADDRESS_SERIALIZED_SIZE];
+        let message = b"cvlr";
+        let instruction = cvlr_secp256k1_instruction(&eth_address, message, 0);
+        let data = &instruction.data;
+
+        // -- as laid out by `new_secp256k1_instruction`, with a recovery id
+        // -- after the signature
+        let eth_address_offset = secp256k1_instruction::DATA_START;
+        let signature_offset =
+            eth_address_offset + secp256k1_instruction::HASHED_PUBKEY_SERIALIZED_SIZE;
+        let message_data_offset =
+            signature_offset + secp256k1_instruction::SIGNATURE_SERIALIZED_SIZE + 1;
+        let expected = secp256k1_instruction::SecpSignatureOffsets {
+            signature_offset: signature_offset as u16,
+            signature_instruction_index: 0,
+            eth_address_offset: eth_address_offset as u16,
+            eth_address_instruction_index: 0,
+            message_data_offset: message_data_offset as u16,
+            message_data_size: message.len() as u16,
+            message_instruction_index: 0,
+        };
+        assert_eq!(data.len(), message_data_offset + message.len());
+        assert_eq!(
+            Secp256k1SignatureOffsets::unpack(data, 0),
+            Some(Secp256k1SignatureOffsets {
+                signature_offset: expected.signature_offset,
+                signature_instruction_index: expected.signature_instruction_index,
+                eth_address_offset: expected.eth_address_offset,
+                eth_address_instruction_index: expected.eth_address_instruction_index,
+                message_data_offset: expected.message_data_offset,
+                message_data_size: expected.message_data_size,
+                message_instruction_index: expected.message_instruction_index,
+            })
+        );
+        assert_eq!(Secp256k1SignatureOffsets::unpack(data, 1), None);
+        assert_eq!(
+            cvlr_secp256k1_signed_message(&instruction, 0, 0),
+            Some((eth_address, &message[..]))
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Model of CPI return data
+//!
+//! Like the runtime, the model keeps the last return data together with the
+//! id of the program that set it. A CPI resets it to empty data of the
+//! callee, so a caller that reads it after a CPI must check who produced it.
+//!
+//! When compiled for the SVM, `set_return_data` and `get_return_data` use this
+//! model through their syscalls. With the `rt` feature, they use it once
+//! [`crate::cvlr_install_syscall_stubs`] is called.
+//!
+//! Return data is set on behalf of [`crate::cvlr_current_program_id`]. Outside
+//! of a CPI, that is the program under verification, so a harness that sets
+//! return data must first call [`crate::cvlr_set_current_program_id`].
+
+use cvlr_asserts::cvlr_assert;
+use solana_program::{program::MAX_RETURN_DATA, pubkey::Pubkey};
+
+use crate::{cvlr_current_program_id, instructions::nondet_bytes};
+
+static mut CVT_RETURN_DATA: Option<(Pubkey, Vec<u8>)> = None;
+
+fn set_return_data(program_id: &Pubkey, data: Vec<u8>) {
+    unsafe {
+        CVT_RETURN_DATA = Some((*program_id, data));
+    }
+}
+
+/// Model of `set_return_data`: sets [data] as the return data of the
+/// current program
+///
+/// Outside of a CPI, [`crate::cvlr_set_current_program_id`] must have been
+/// called.
+pub fn cvlr_set_return_data(data: &[u8]) {
+    cvlr_assert!(
+        data.len() <= MAX_RETURN_DATA,
+        "ReturnDataTooLarge: return data exceeds MAX_RETURN_DATA"
+    );
+    set_return_data(&cvlr_current_program_id(), data.to_vec());
+}
+
+/// Model of `get_return_data`: returns the last return data and the id of
+/// the program that set it, or `None` if it is empty
+#[allow(static_mut_refs)]
+pub fn cvlr_get_return_data() -> Option<(Pubkey, Vec<u8>)> {
+    unsafe { CVT_RETURN_DATA.clone() }.filter(|(_, data)| !data.is_empty())
+}
+
+/// Returns the id of the program that set the last return data, if any
+#[allow(static_mut_refs)]
+pub fn cvlr_return_data_program_id() -> Option<Pubkey> {
+    unsafe { CVT_RETURN_DATA.as_ref() }.map(|(program_id, _)| *program_id)
+}
+
+/// Sets nondet return data of at most `MAX_RETURN_DATA` bytes on behalf of
+/// [program_id]
+///
+/// CPI summaries call it for programs that return data.
+pub fn cvlr_set_nondet_return_data(program_id: &Pubkey) {
+    set_return_data(program_id, nondet_bytes(MAX_RETURN_DATA));
+}
+
+/// Resets the return data to empty data of [program_id], as the runtime
+/// does when it invokes a program
+pub fn cvlr_reset_return_data(program_id: &Pubkey) {
+    set_return_data(program_id, Vec::new());
+}
@@ -0,0 +1,151 @@
+//! Models of the Solana syscalls used by `Sysvar::get()`, PDA derivation and
+//! return data
+//!
+//! When compiled for the SVM, this module defines the syscall symbols so that
+//! unmodified program code uses the models of this crate. With the `rt`
+//! feature, the sysvar and return data models are installed as
+//! [`SyscallStubs`].
+//!
+//! [`SyscallStubs`]: solana_program::program_stubs::SyscallStubs
+
+use solana_program::entrypoint::SUCCESS;
+
+/// Writes [val] at [var_addr], as the runtime does for sysvar syscalls
+#[inline(always)]
+unsafe fn write_sysvar<T>(var_addr: *mut u8, val: T) -> u64 {
+    (var_addr as *mut T).write(val);
+    SUCCESS
+}
+
+#[inline(always)]
+fn get_clock_sysvar(var_addr: *mut u8) -> u64 {
+    unsafe { write_sysvar(var_addr, crate::cvlr_nondet_clock()) }
+}
+
+#[inline(always)]
+fn get_rent_sysvar(var_addr: *mut u8) -> u64 {
+    unsafe { write_sysvar(var_addr, crate::cvlr_nondet_rent()) }
+}
+
+#[inline(always)]
+fn get_epoch_schedule_sysvar(var_addr: *mut u8) -> u64 {
+    unsafe { write_sysvar(var_addr, crate::cvlr_nondet_epoch_schedule()) }
+}
+
+#[cfg(target_os = "solana")]
+mod syscall_impls {
+    use solana_program::{entrypoint::SUCCESS, pubkey::Pubkey};
+
+    /// Error code of the PDA syscalls when no valid address exists
+    const INVALID_SEEDS: u64 = 1;
+
+    #[no_mangle]
+    extern "C" fn sol_get_clock_sysvar(var_addr: *mut u8) -> u64 {
+        super::get_clock_sysvar(var_addr)
+    }
+    #[no_mangle]
+    extern "C" fn sol_get_rent_sysvar(var_addr: *mut u8) -> u64 {
+        super::get_rent_sysvar(var_addr)
+    }
+    #[no_mangle]
+    extern "C" fn sol_get_epoch_schedule_sysvar(var_addr: *mut u8) -> u64 {
+        super::get_epoch_schedule_sysvar(var_addr)
+    }
+    #[no_mangle]
+    unsafe extern "C" fn sol_set_return_data(data: *const u8, length: u64) {
+        let data = std::slice::from_raw_parts(data, length as usize);
+        crate::cvlr_set_return_data(data)
+    }
+    #[no_mangle]
+    unsafe extern "C" fn sol_get_return_data(
+        data: *mut u8,
+        length: u64,
+        program_id: *mut Pubkey,
+    ) -> u64 {
+        match crate::cvlr_get_return_data() {
+            Some((id, return_data)) => {
+                let length = (length as usize).min(return_data.len());
+                if length != 0 {
+                    std::ptr::copy_nonoverlapping(return_data.as_ptr(), data, length);
+                    program_id.write(id);
+                }
+                return_data.len() as u64
+            }
+            None => 0,
+        }
+    }
+    #[no_mangle]
+    unsafe extern "C" fn sol_create_program_address(
+        seeds_addr: *const u8,
+        seeds_len: u64,
+        program_id_addr: *const u8,
+        address_bytes_addr: *mut u8,
+    ) -> u64 {
+        let seeds = std::slice::from_raw_parts(seeds_addr as *const &[u8], seeds_len as usize);
+        let program_id = &*(program_id_addr as *const Pubkey);
+        match crate::cvlr_create_program_address(seeds, program_id) {
+            Ok(address) => {
+                (address_bytes_addr as *mut Pubkey).write(address);
+                SUCCESS
+            }
+            Err(_) => INVALID_SEEDS,
+        }
+    }
+    #[no_mangle]
+    unsafe extern "C" fn sol_try_find_program_address(
+        seeds_addr: *const u8,
+        seeds_len: u64,
+        program_id_addr: *const u8,
+        address_bytes_addr: *mut u8,
+        bump_seed_addr: *mut u8,
+    ) -> u64 {
+        let seeds = std::slice::from_raw_parts(seeds_addr as *const &[u8], seeds_len as usize);
+        let program_id = &*(program_id_addr as *const Pubkey);
+        match crate::cvlr_try_find_program_address(seeds, program_id) {
+            Some((address, bump)) => {
+                (address_bytes_addr as *mut Pubkey).write(address);
+                *bump_seed_addr = bump;
+                SUCCESS
+            }
+            None => INVALID_SEEDS,
+        }
+    }
+}
+
+#[cfg(feature = "rt")]
+mod rt_impls {
+    use solana_program::{program_stubs::SyscallStubs, pubkey::Pubkey};
+
+    pub struct CvlrSyscallStubs;
+
+    impl SyscallStubs for CvlrSyscallStubs {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            super::get_clock_sysvar(var_addr)
+        }
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            super::get_rent_sysvar(var_addr)
+        }
+        fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+            super::get_epoch_schedule_sysvar(var_addr)
+        }
+        fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+            crate::cvlr_get_return_data()
+        }
+        fn sol_set_return_data(&self, data: &[u8]) {
+            crate::cvlr_set_return_data(data)
+        }
+    }
+}
+
+/// Installs the syscall models of this crate as the native syscall stubs
+///
+/// Natively, `Sysvar::get()` goes through `program_stubs` instead of the
+/// syscall symbols, so this must be called once before running a harness.
+///
+/// Once the stubs are installed, `set_return_data` records the return data
+/// on behalf of [`crate::cvlr_current_program_id`]. Outside of a CPI, it
+/// fails unless the harness called [`crate::cvlr_set_current_program_id`].
+#[cfg(feature = "rt")]
+pub fn cvlr_install_syscall_stubs() {
+    solana_program::program_stubs::set_syscall_stubs(Box::new(rt_impls::CvlrSyscallStubs));
+}
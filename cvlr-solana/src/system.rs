@@ -0,0 +1,101 @@
+//! Summary of the System program
+use arrayref::{array_ref, array_refs};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_instruction::{SystemError, MAX_PERMITTED_DATA_LENGTH},
+    system_program,
+};
+
+use crate::{cvlr_cpi_account_info, cvlr_cpi_default};
+
+const CREATE_ACCOUNT: u32 = 0;
+const ASSIGN: u32 = 1;
+const TRANSFER: u32 = 2;
+const ALLOCATE: u32 = 8;
+
+/// Move [lamports] from [from_info] to [to_info]
+fn transfer_lamports(
+    from_info: &AccountInfo,
+    to_info: &AccountInfo,
+    lamports: u64,
+) -> ProgramResult {
+    if from_info.lamports() < lamports {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    // non-op if self-transfer
+    if from_info.key != to_info.key {
+        **from_info.try_borrow_mut_lamports()? -= lamports;
+        let to_lamports = to_info.lamports().checked_add(lamports).unwrap();
+        **to_info.try_borrow_mut_lamports()? = to_lamports;
+    }
+    Ok(())
+}
+
+fn system_error(error: SystemError) -> ProgramError {
+    ProgramError::Custom(error as u32)
+}
+
+/// Allocates [space] bytes of zeroed data to [info], which must not be in use
+pub(crate) fn allocate(info: &AccountInfo, space: u64) -> ProgramResult {
+    if !info.data_is_empty() || !system_program::check_id(info.owner) {
+        return Err(system_error(SystemError::AccountAlreadyInUse));
+    }
+    if space > MAX_PERMITTED_DATA_LENGTH {
+        return Err(system_error(SystemError::InvalidAccountDataLength));
+    }
+    // -- fails beyond MAX_PERMITTED_DATA_INCREASE, like any realloc
+    info.realloc(space as usize, true)
+}
+
+/// Summary of a CPI into the System program
+///
+/// Only `CreateAccount`, `Assign`, `Transfer` and `Allocate` are modelled.
+/// Like the System program, they fail with `AccountAlreadyInUse` on an
+/// account in use. The data of an allocated account is reallocated, so it
+/// must be laid out as by the runtime (e.g., by
+/// [`crate::cvlr_deserialize_nondet_accounts`]) and grows by at most
+/// `MAX_PERMITTED_DATA_INCREASE` bytes.
+pub fn system_program_cpi(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let data = &instruction.data;
+    if data.len() < 4 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let account = |index| cvlr_cpi_account_info(instruction, account_infos, index);
+    let discriminant = u32::from_le_bytes(*array_ref![data, 0, 4]);
+    match (discriminant, &data[4..]) {
+        (CREATE_ACCOUNT, args) if args.len() >= 48 => {
+            let (lamports, space, owner) = array_refs![array_ref![args, 0, 48], 8, 8, 32];
+            let (from_info, to_info) = (account(0)?, account(1)?);
+            if to_info.lamports() > 0 {
+                return Err(system_error(SystemError::AccountAlreadyInUse));
+            }
+            allocate(to_info, u64::from_le_bytes(*space))?;
+            to_info.assign(&Pubkey::new_from_array(*owner));
+            transfer_lamports(from_info, to_info, u64::from_le_bytes(*lamports))
+        }
+        (ASSIGN, args) if args.len() >= 32 => {
+            account(0)?.assign(&Pubkey::new_from_array(*array_ref![args, 0, 32]));
+            Ok(())
+        }
+        (TRANSFER, args) if args.len() >= 8 => transfer_lamports(
+            account(0)?,
+            account(1)?,
+            u64::from_le_bytes(*array_ref![args, 0, 8]),
+        ),
+        (ALLOCATE, args) if args.len() >= 8 => {
+            allocate(account(0)?, u64::from_le_bytes(*array_ref![args, 0, 8]))
+        }
+        (CREATE_ACCOUNT | ASSIGN | TRANSFER | ALLOCATE, _) => {
+            Err(ProgramError::InvalidInstructionData)
+        }
+        _ => cvlr_cpi_default(instruction, account_infos, signers_seeds),
+    }
+}
@@ -0,0 +1,145 @@
+use cvlr_asserts::cvlr_assume;
+use cvlr_nondet::nondet;
+use solana_program::{
+    clock::{Epoch, Slot},
+    epoch_schedule::{EpochSchedule, MINIMUM_SLOTS_PER_EPOCH},
+    rent::{Rent, DEFAULT_EXEMPTION_THRESHOLD},
+    slot_hashes::SlotHashes,
+    stake_history::{StakeHistory, StakeHistoryEntry},
+};
+
+use crate::{cvlr_clock_snapshot, cvlr_nondet_hash};
+
+static mut CVT_RENT: Option<Rent> = None;
+static mut CVT_EPOCH_SCHEDULE: Option<EpochSchedule> = None;
+
+/// Forgets the [`Rent`] and [`EpochSchedule`] drawn so far
+pub fn cvlr_sysvars_reset() {
+    unsafe {
+        CVT_RENT = None;
+        CVT_EPOCH_SCHEDULE = None;
+    }
+}
+
+/// Returns a nondet [`Rent`]
+///
+/// The rent is drawn on the first call, and every later call returns the
+/// same value until [`cvlr_sysvars_reset`] is called.
+///
+/// `exemption_threshold` is kept at its default value. Note that
+/// [`Rent::minimum_balance`] still multiplies by it in floating point.
+pub fn cvlr_nondet_rent() -> Rent {
+    unsafe { CVT_RENT }.unwrap_or_else(|| {
+        let burn_percent: u8 = nondet();
+        cvlr_assume!(burn_percent <= 100);
+        let rent = Rent {
+            lamports_per_byte_year: nondet(),
+            exemption_threshold: DEFAULT_EXEMPTION_THRESHOLD,
+            burn_percent,
+        };
+        unsafe {
+            CVT_RENT = Some(rent);
+        }
+        rent
+    })
+}
+
+/// Returns a nondet [`EpochSchedule`]
+///
+/// The schedule is drawn on the first call, and every later call returns the
+/// same value until [`cvlr_sysvars_reset`] is called.
+pub fn cvlr_nondet_epoch_schedule() -> EpochSchedule {
+    unsafe { CVT_EPOCH_SCHEDULE }.unwrap_or_else(|| {
+        let epoch_schedule = nondet_epoch_schedule();
+        unsafe {
+            CVT_EPOCH_SCHEDULE = Some(epoch_schedule);
+        }
+        epoch_schedule
+    })
+}
+
+/// `first_normal_epoch` and `first_normal_slot` are consistent with `warmup`
+/// as computed by [`EpochSchedule::custom`]: with warmup, epochs start at
+/// `MINIMUM_SLOTS_PER_EPOCH` slots and double until they reach the next power
+/// of two of `slots_per_epoch`.
+fn nondet_epoch_schedule() -> EpochSchedule {
+    let slots_per_epoch: u64 = nondet();
+    cvlr_assume!(slots_per_epoch >= MINIMUM_SLOTS_PER_EPOCH);
+
+    let warmup: bool = nondet();
+    let (first_normal_epoch, first_normal_slot) = if warmup {
+        let first_normal_epoch: Epoch = nondet();
+        cvlr_assume!(
+            first_normal_epoch < (u64::BITS - MINIMUM_SLOTS_PER_EPOCH.trailing_zeros()) as u64
+        );
+        // -- slots_per_epoch.next_power_of_two()
+        let next_power_of_two = MINIMUM_SLOTS_PER_EPOCH << first_normal_epoch;
+        cvlr_assume!(next_power_of_two / 2 < slots_per_epoch);
+        cvlr_assume!(slots_per_epoch <= next_power_of_two);
+        (
+            first_normal_epoch,
+            next_power_of_two - MINIMUM_SLOTS_PER_EPOCH,
+        )
+    } else {
+        (0, 0)
+    };
+
+    EpochSchedule {
+        slots_per_epoch,
+        leader_schedule_slot_offset: nondet(),
+        warmup,
+        first_normal_epoch,
+        first_normal_slot,
+    }
+}
+
+/// Returns a nondet [`SlotHashes`] with at most `N` entries
+///
+/// As maintained by the runtime, entries are sorted by strictly decreasing
+/// slot. If the clock model is initialized, all entries are older than the
+/// current slot.
+pub fn cvlr_nondet_slot_hashes<const N: usize>() -> SlotHashes {
+    let len: usize = nondet();
+    cvlr_assume!(len <= N);
+
+    let mut prev_slot = cvlr_clock_snapshot().slot;
+    let mut entries = Vec::with_capacity(len);
+    for _ in 0..len {
+        let slot: Slot = nondet();
+        if let Some(prev_slot) = prev_slot {
+            cvlr_assume!(slot < prev_slot);
+        }
+        prev_slot = Some(slot);
+        entries.push((slot, cvlr_nondet_hash()));
+    }
+    entries.into_iter().collect()
+}
+
+/// Returns a nondet [`StakeHistory`] with at most `N` entries
+///
+/// As maintained by the runtime, entries are sorted by strictly decreasing
+/// epoch.
+pub fn cvlr_nondet_stake_history<const N: usize>() -> StakeHistory {
+    let len: usize = nondet();
+    cvlr_assume!(len <= N);
+
+    let mut prev_epoch: Option<Epoch> = None;
+    let mut stake_history = StakeHistory::default();
+    for _ in 0..len {
+        let epoch: Epoch = nondet();
+        if let Some(prev_epoch) = prev_epoch {
+            cvlr_assume!(epoch < prev_epoch);
+        }
+        prev_epoch = Some(epoch);
+        // -- epochs are added oldest last, so `add` always appends
+        stake_history.add(
+            epoch,
+            StakeHistoryEntry {
+                effective: nondet(),
+                activating: nondet(),
+                deactivating: nondet(),
+            },
+        );
+    }
+    stake_history
+}
@@ -2,12 +2,22 @@
 ///
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
+    program_error::ProgramError, pubkey::Pubkey, system_instruction::SystemError,
 };
 
 use cvlr_asserts::cvlr_assume;
 use cvlr_mathint::NativeInt as MathInt;
 
+use crate::{cvlr_cpi_account_info, cvlr_cpi_default, cvlr_find_program_address, system::allocate};
+
+/// Length of a token account without extensions
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Id of the SPL Associated Token Account program
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
 /// Unpack only amount from account [info] base
 pub fn spl_token_account_get_amount(info: &AccountInfo) -> u64 {
     let data = info.data.borrow_mut();
@@ -19,6 +29,16 @@ pub fn spl_token_account_get_amount(info: &AccountInfo) -> u64 {
     val
 }
 
+/// Unpack only mint and owner from account [info] base
+pub fn spl_token_account_get_mint_and_owner(info: &AccountInfo) -> (Pubkey, Pubkey) {
+    let data = info.data.borrow();
+    let (mint, owner) = array_refs![array_ref![*data, 0, 64], 32, 32];
+    (
+        Pubkey::new_from_array(*mint),
+        Pubkey::new_from_array(*owner),
+    )
+}
+
 /// Pack only [amount] from account [info] base
 pub fn spl_token_account_set_amount(amount: u64, info: &AccountInfo) {
     let mut data = info.data.borrow_mut();
@@ -36,6 +56,32 @@ pub fn spl_token_account_set_amount(amount: u64, info: &AccountInfo) {
     *amount_dst = amount.to_le_bytes();
 }
 
+/// Pack an initialized account of [mint] owned by [owner] with no tokens into
+/// account [info] base
+pub fn spl_token_account_init(mint: &Pubkey, owner: &Pubkey, info: &AccountInfo) {
+    let mut data = info.data.borrow_mut();
+    let dst = array_mut_ref![*data, 0, 165];
+    let (
+        mint_dst,
+        owner_dst,
+        amount_dst,
+        delegate_dst,
+        state_dst,
+        is_native_dst,
+        delegated_amount_dst,
+        close_authority_dst,
+    ) = mut_array_refs![dst, 32, 32, 8, 36, 1, 12, 8, 36];
+    mint_dst.copy_from_slice(mint.as_ref());
+    owner_dst.copy_from_slice(owner.as_ref());
+    *amount_dst = 0u64.to_le_bytes();
+    *delegate_dst = [0; 36];
+    // -- AccountState::Initialized
+    *state_dst = [1];
+    *is_native_dst = [0; 12];
+    *delegated_amount_dst = 0u64.to_le_bytes();
+    *close_authority_dst = [0; 36];
+}
+
 /// Unpack only supply from [mint] base
 pub fn spl_mint_get_supply(mint: &AccountInfo) -> u64 {
     let data = mint.data.borrow_mut();
@@ -179,6 +225,103 @@ pub fn spl_close_account<'a>(
     Ok(())
 }
 
+macro_rules! impl_token_cpi {
+    ($name:ident, $token:ident, $transfer:ident) => {
+        /// Summary of a CPI into the token program
+        ///
+        /// Only transfers, mints and burns are modelled.
+        #[allow(deprecated)]
+        pub fn $name(
+            instruction: &Instruction,
+            account_infos: &[AccountInfo],
+            signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            use $token::instruction::TokenInstruction;
+            let account = |index| cvlr_cpi_account_info(instruction, account_infos, index);
+            match TokenInstruction::unpack(&instruction.data)? {
+                TokenInstruction::Transfer { amount } => {
+                    $transfer(account(0)?, account(1)?, account(2)?, amount)
+                }
+                TokenInstruction::TransferChecked { amount, .. } => {
+                    $transfer(account(0)?, account(2)?, account(3)?, amount)
+                }
+                TokenInstruction::MintTo { amount }
+                | TokenInstruction::MintToChecked { amount, .. } => {
+                    spl_mint_to(account(0)?, account(1)?, account(2)?, amount)
+                }
+                TokenInstruction::Burn { amount }
+                | TokenInstruction::BurnChecked { amount, .. } => {
+                    spl_burn(account(1)?, account(0)?, account(2)?, amount)
+                }
+                _ => cvlr_cpi_default(instruction, account_infos, signers_seeds),
+            }
+        }
+    };
+}
+
+impl_token_cpi!(spl_token_cpi, spl_token, spl_token_transfer);
+impl_token_cpi!(spl_token_2022_cpi, spl_token_2022, spl_token_2022_transfer);
+
+/// Summary of a CPI into the Associated Token Account program
+///
+/// `Create` allocates the associated token account with the System program
+/// summary and initializes it without tokens, so it fails if the account is
+/// in use. `CreateIdempotent` leaves an existing associated token account
+/// unchanged, after checking its mint and owner.
+pub fn spl_associated_token_account_cpi(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let account = |index| cvlr_cpi_account_info(instruction, account_infos, index);
+    // -- Create, CreateIdempotent, RecoverNested
+    let create = match instruction.data.first() {
+        None | Some(0) => true,
+        Some(1) => false,
+        _ => return cvlr_cpi_default(instruction, account_infos, signers_seeds),
+    };
+
+    let ata_info = account(1)?;
+    let wallet_info = account(2)?;
+    let mint_info = account(3)?;
+    let token_program_info = account(5)?;
+
+    let (address, _) = cvlr_find_program_address(
+        &[
+            wallet_info.key.as_ref(),
+            token_program_info.key.as_ref(),
+            mint_info.key.as_ref(),
+        ],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    if *ata_info.key != address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if ata_info.owner == token_program_info.key && ata_info.data_len() >= TOKEN_ACCOUNT_LEN {
+        if create {
+            // -- the System program fails to allocate an account in use
+            return Err(ProgramError::Custom(
+                SystemError::AccountAlreadyInUse as u32,
+            ));
+        }
+        let (mint, owner) = spl_token_account_get_mint_and_owner(ata_info);
+        if owner != *wallet_info.key {
+            // -- AssociatedTokenAccountError::InvalidOwner
+            return Err(ProgramError::Custom(0));
+        }
+        if mint != *mint_info.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        return Ok(());
+    }
+
+    allocate(ata_info, TOKEN_ACCOUNT_LEN as u64)?;
+    ata_info.assign(token_program_info.key);
+    spl_token_account_init(mint_info.key, wallet_info.key, ata_info);
+    Ok(())
+}
+
 macro_rules! impl_nondet_mint {
     ($name:ident, $mint_ty:ty) => {
         pub fn $name() -> $mint_ty {